@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use bevy_event_entities::{
     event_listener::{
-        event_listener_system_configs, AddCallbackExt, Listenable, Listener, On, Target,
+        event_listener_system_configs, AddCallbackExt, Listenable, Listener, On,
+        RegisterCallbackExt, Target,
     },
     send_event, EventEntities,
 };
@@ -56,3 +57,37 @@ fn nested((run, depth, n): (bool, usize, usize)) {
         schedule.run(&mut world);
     }
 }
+
+#[divan::bench(args = [
+    (false, 100, 100), (true, 100, 100),
+    (false, 1_000, 1), (true, 1_000, 1),
+    (false, 1, 1_000), (true, 1, 1_000),
+])]
+fn nested_shared((run, depth, n): (bool, usize, usize)) {
+    let (mut world, mut schedule) = setup();
+
+    let callback = world.register_callback(|input: Listener<&MyEvent>| {
+        assert_eq!(69, input.event().num);
+    });
+
+    let mut entity = world.spawn_empty();
+    for _ in 0..n {
+        entity.add_callback(On::<MyEvent>::run(callback.clone()));
+    }
+    let mut entity = entity.id();
+    for _ in 0..depth {
+        let mut child = world.spawn_empty();
+        for _ in 0..n {
+            child.add_callback(On::<MyEvent>::run(callback.clone()));
+        }
+        let child = child.id();
+        world.entity_mut(entity).add_child(child);
+        entity = child;
+    }
+
+    send_event(&mut world, MyEvent { num: 69 }).insert(Target(entity));
+
+    if run {
+        schedule.run(&mut world);
+    }
+}
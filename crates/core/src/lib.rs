@@ -1,22 +1,32 @@
 use std::{
+    any::TypeId,
+    collections::VecDeque,
+    fmt::Debug,
+    hash::Hash,
     iter::Chain,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
     slice::Iter,
+    sync::{Arc, Mutex},
 };
 
 use bevy_app::prelude::*;
 use bevy_ecs::{
+    entity::Entities,
     prelude::*,
     query::{QueryFilter, ReadOnlyQueryData},
     schedule::{ScheduleLabel, SystemConfigs},
-    system::{EntityCommands, SystemParam},
+    system::{BoxedSystem, EntityCommands, SystemParam},
 };
+use bevy_log::{trace, warn};
 use bevy_reflect::Reflect;
-use bevy_utils::intern::Interned;
+use bevy_utils::{intern::Interned, HashMap};
 
 pub mod prelude {
     pub use crate::{
-        EntityEventReader, EventEntities, EventPlugin, QueryEventReader, SendEventExt,
+        AddEventPipelineExt, CancelEventExt, EntityEventReader, EventBatch, EventBatchReader,
+        EventCascade, EventCascadePlugin, EventEntities, EventPlugin, Intercept, QueryEventReader,
+        SendEventExt, SendEventTransactionExt, Sticky,
     };
 }
 
@@ -25,7 +35,11 @@ pub struct EventSystems;
 
 pub fn event_system_configs() -> SystemConfigs {
     IntoSystemConfigs::into_configs(
-        (update_events.run_if(any_events), reset_event_update_signal)
+        (
+            intercept_queued_events,
+            update_events.run_if(any_events),
+            reset_event_update_signal,
+        )
             .chain()
             .in_set(EventSystems),
     )
@@ -34,14 +48,21 @@ pub fn event_system_configs() -> SystemConfigs {
 pub struct EventPlugin {
     update_schedule: Interned<dyn ScheduleLabel>,
     signal_schedule: Interned<dyn ScheduleLabel>,
+    // `Plugin::build` only gets `&self`, so the interceptors have to be taken out of a `Mutex`.
+    interceptors: Mutex<Vec<BoxedInterceptor>>,
 }
 
 impl Plugin for EventPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EventEntities>();
         app.init_resource::<EventUpdateSignal>();
-        app.add_systems(self.update_schedule.clone(), event_system_configs());
-        app.add_systems(self.signal_schedule.clone(), signal_event_update);
+        app.init_resource::<EventInterceptors>();
+        let interceptors = std::mem::take(&mut *self.interceptors.lock().unwrap());
+        app.world
+            .resource_mut::<EventInterceptors>()
+            .extend(interceptors);
+        app.add_systems(self.update_schedule, event_system_configs());
+        app.add_systems(self.signal_schedule, signal_event_update);
     }
 }
 
@@ -50,6 +71,7 @@ impl Default for EventPlugin {
         Self {
             update_schedule: PostUpdate.intern(),
             signal_schedule: FixedPostUpdate.intern(),
+            interceptors: Mutex::default(),
         }
     }
 }
@@ -59,8 +81,284 @@ impl EventPlugin {
         Self {
             update_schedule: update_schedule.intern(),
             signal_schedule: signal_schedule.intern(),
+            interceptors: Mutex::default(),
         }
     }
+
+    /// Add an interceptor that runs for every event right after it's sent, before any reader sees it.
+    ///
+    /// Interceptors run in the order they are added. See [`EventInterceptors`] for more details.
+    pub fn with_interceptor<O, M>(self, interceptor: impl IntoSystem<Entity, O, M>) -> Self
+    where
+        O: Into<Intercept> + 'static,
+    {
+        self.interceptors
+            .lock()
+            .unwrap()
+            .push(EventInterceptors::box_interceptor(interceptor));
+        self
+    }
+}
+
+/// The system set containing every stage of the event pipeline for `T`.
+#[derive(SystemSet)]
+pub struct EventPipelineSystems<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for EventPipelineSystems<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for EventPipelineSystems<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EventPipelineSystems<T> {}
+
+impl<T> PartialEq for EventPipelineSystems<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for EventPipelineSystems<T> {}
+
+impl<T> Hash for EventPipelineSystems<T> {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+impl<T> Debug for EventPipelineSystems<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EventPipelineSystems<{}>", std::any::type_name::<T>())
+    }
+}
+
+/// The system set of a single stage in the event pipeline for `T`.
+///
+/// Every stage runs after the previous one, with commands applied in between.
+#[derive(SystemSet)]
+pub struct EventPipelineStage<T> {
+    stage: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> EventPipelineStage<T> {
+    pub fn new(stage: usize) -> Self {
+        Self {
+            stage,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+}
+
+impl<T> Clone for EventPipelineStage<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EventPipelineStage<T> {}
+
+impl<T> PartialEq for EventPipelineStage<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.stage == other.stage
+    }
+}
+
+impl<T> Eq for EventPipelineStage<T> {}
+
+impl<T> Hash for EventPipelineStage<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.stage.hash(state);
+    }
+}
+
+impl<T> Debug for EventPipelineStage<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EventPipelineStage<{}>({})",
+            std::any::type_name::<T>(),
+            self.stage
+        )
+    }
+}
+
+/// The number of stages in each event pipeline.
+#[derive(Resource, Default)]
+struct EventPipelineStages(HashMap<(TypeId, Interned<dyn ScheduleLabel>), usize>);
+
+pub trait AddEventPipelineExt {
+    /// Add an event pipeline for `T` to the `PostUpdate` schedule.
+    ///
+    /// Each stage runs in order, and sees the events in the order they were sent.
+    /// Events that were consumed (despawned) by an earlier stage are skipped by later stages.
+    ///
+    /// ```ignore
+    /// app.add_event_pipeline::<Attack>()
+    ///     .stage(block_attack)
+    ///     .stage(process_attack);
+    /// ```
+    fn add_event_pipeline<T: 'static>(&mut self) -> EventPipeline<'_, T>;
+
+    /// Add an event pipeline for `T` to the given schedule.
+    fn add_event_pipeline_to<T: 'static>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> EventPipeline<'_, T>;
+}
+
+impl AddEventPipelineExt for App {
+    fn add_event_pipeline<T: 'static>(&mut self) -> EventPipeline<'_, T> {
+        self.add_event_pipeline_to(PostUpdate)
+    }
+
+    fn add_event_pipeline_to<T: 'static>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> EventPipeline<'_, T> {
+        let schedule = schedule.intern();
+        self.configure_sets(
+            schedule,
+            EventPipelineSystems::<T>::default().before(EventSystems),
+        );
+        EventPipeline {
+            app: self,
+            schedule,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Builder for an event pipeline, see [`AddEventPipelineExt::add_event_pipeline`].
+pub struct EventPipeline<'a, T> {
+    app: &'a mut App,
+    schedule: Interned<dyn ScheduleLabel>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: 'static> EventPipeline<'a, T> {
+    /// Add a stage to the end of the pipeline.
+    pub fn stage<M>(self, systems: impl IntoSystemConfigs<M>) -> Self {
+        let stage = {
+            let mut stages = self
+                .app
+                .world
+                .get_resource_or_insert_with(EventPipelineStages::default);
+            let count = stages
+                .0
+                .entry((TypeId::of::<T>(), self.schedule))
+                .or_default();
+            *count += 1;
+            *count - 1
+        };
+
+        let set = EventPipelineStage::<T>::new(stage);
+        self.app
+            .configure_sets(
+                self.schedule,
+                set.in_set(EventPipelineSystems::<T>::default()),
+            )
+            .add_systems(self.schedule, systems.in_set(set));
+        if stage > 0 {
+            self.app.configure_sets(
+                self.schedule,
+                set.after(EventPipelineStage::<T>::new(stage - 1)),
+            );
+        }
+        self
+    }
+}
+
+/// Systems in this schedule are rerun until they stop sending new events, see [`EventCascadePlugin`].
+#[derive(ScheduleLabel, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EventCascade;
+
+/// Runs the [`EventCascade`] schedule until no new events are sent, so that chains of events like
+/// `Attack -> Kill -> DropLoot` are fully processed within a single frame.
+pub struct EventCascadePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+    max_iterations: usize,
+}
+
+impl Plugin for EventCascadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_schedule(EventCascade);
+        app.insert_resource(EventCascadeSettings {
+            max_iterations: self.max_iterations,
+        });
+        app.add_systems(self.schedule, run_event_cascade.before(EventSystems));
+    }
+}
+
+impl Default for EventCascadePlugin {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            max_iterations: EventCascadeSettings::default().max_iterations,
+        }
+    }
+}
+
+impl EventCascadePlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of times the [`EventCascade`] schedule is run each frame.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct EventCascadeSettings {
+    /// The maximum number of times the [`EventCascade`] schedule is run each frame.
+    pub max_iterations: usize,
+}
+
+impl Default for EventCascadeSettings {
+    fn default() -> Self {
+        Self { max_iterations: 16 }
+    }
+}
+
+/// Run the [`EventCascade`] schedule until no new events are pushed to the `EventEntities` resource.
+///
+/// Logs a warning if there are still new events after [`EventCascadeSettings::max_iterations`].
+pub fn run_event_cascade(world: &mut World) {
+    let max_iterations = world
+        .get_resource::<EventCascadeSettings>()
+        .copied()
+        .unwrap_or_default()
+        .max_iterations;
+
+    let mut event_count = world.resource::<EventEntities>().event_count();
+    for _ in 0..max_iterations {
+        if world.try_run_schedule(EventCascade).is_err() {
+            return;
+        }
+        intercept_queued_events(world);
+        let new_event_count = world.resource::<EventEntities>().event_count();
+        if new_event_count == event_count {
+            return;
+        }
+        event_count = new_event_count;
+    }
+    warn!(
+        "event cascade did not settle after {max_iterations} iterations, the remaining events will be processed next frame"
+    );
 }
 
 #[derive(Resource, Default)]
@@ -74,8 +372,14 @@ pub fn reset_event_update_signal(mut signal: ResMut<EventUpdateSignal>) {
     signal.0 = false;
 }
 
-pub fn any_events(events: Res<EventEntities>) -> bool {
-    !events.events_a.is_empty() || !events.events_b.is_empty()
+/// Returns `true` if there are events, including the ones queued by [`send_event`] that haven't been intercepted yet.
+pub fn any_events(
+    events: Res<EventEntities>,
+    interceptors: Option<Res<EventInterceptors>>,
+) -> bool {
+    !events.events_a.is_empty()
+        || !events.events_b.is_empty()
+        || interceptors.is_some_and(|interceptors| !interceptors.queued.is_empty())
 }
 
 pub fn new_events(mut events: EntityEventReader) -> bool {
@@ -98,34 +402,284 @@ pub fn update_events(world: &mut World) {
     });
 }
 
-pub fn send_event(world: &mut World, event: impl Bundle) -> EntityWorldMut {
+/// Spawn an event and push it to the `EventEntities` resource.
+///
+/// While there are [`EventInterceptors`], the event is queued until [`intercept_queued_events`] runs,
+/// so components inserted into the returned entity are seen by the interceptors.
+/// Use [`try_send_event`] to intercept the event right away.
+pub fn send_event(world: &mut World, event: impl Bundle) -> EntityWorldMut<'_> {
     let event = world.spawn(event).id();
-    world.resource_mut::<EventEntities>().push(event);
+    queue_event(world, event);
     world.entity_mut(event)
 }
 
+/// Spawn an event, run the [`EventInterceptors`] right away and push it to the `EventEntities` resource.
+///
+/// Returns `None` if the event was vetoed by an interceptor.
+pub fn try_send_event(world: &mut World, event: impl Bundle) -> Option<EntityWorldMut<'_>> {
+    let event = world.spawn(event).id();
+    push_event(world, event);
+    world.get_entity_mut(event)
+}
+
+/// Send a [`Sticky`] event, replacing the previous sticky event of the same kind.
+pub fn send_sticky_event<E: Component>(world: &mut World, event: E) -> EntityWorldMut<'_> {
+    send_event(world, (event, Sticky::new::<E>()))
+}
+
+/// Push an already spawned event to the `EventEntities` resource.
+///
+/// The event is passed through the [`EventInterceptors`] first, and despawned if it's vetoed.
+/// If the event is [`Sticky`], it will replace the previous sticky event of the same kind.
+pub fn push_event(world: &mut World, event: Entity) {
+    push_events(world, [event]);
+}
+
+/// Push already spawned events to the `EventEntities` resource, intercepting them together.
+fn push_events(world: &mut World, events: impl IntoIterator<Item = Entity>) {
+    let intercept = world
+        .get_resource::<EventInterceptors>()
+        .is_some_and(|interceptors| interceptors.running || !interceptors.is_empty());
+    match intercept {
+        true => intercept_events(world, events),
+        false => {
+            for event in events {
+                push_event_unintercepted(world, event);
+            }
+        }
+    }
+}
+
+/// Push an event sent with [`send_event`], once the caller is done with it.
+///
+/// While there are [`EventInterceptors`], the event is queued until [`intercept_queued_events`] runs,
+/// so that the interceptors see every component the sender inserted. Otherwise it's pushed right away.
+fn queue_event(world: &mut World, event: Entity) {
+    let queue = world
+        .get_resource::<EventInterceptors>()
+        .is_some_and(|interceptors| !interceptors.running && !interceptors.is_empty());
+    match queue {
+        true => world.resource_mut::<EventInterceptors>().queued.push(event),
+        false => push_event(world, event),
+    }
+}
+
+/// Intercept the events that were queued by [`send_event`], and push them to the `EventEntities` resource.
+///
+/// This runs before the events are updated in [`event_system_configs`], and after every iteration of the
+/// [`EventCascade`]. Add it to your own schedule to let readers see the queued events earlier.
+pub fn intercept_queued_events(world: &mut World) {
+    let Some(mut interceptors) = world.get_resource_mut::<EventInterceptors>() else {
+        return;
+    };
+    if interceptors.queued.is_empty() {
+        return;
+    }
+    let queued = std::mem::take(&mut interceptors.queued);
+    intercept_events(world, queued);
+}
+
+fn push_event_unintercepted(world: &mut World, event: Entity) {
+    let sticky = world.get::<Sticky>(event).copied();
+    let mut events = world.resource_mut::<EventEntities>();
+    let Some(sticky) = sticky else {
+        events.push(event);
+        return;
+    };
+    if let Some(replaced) = events.push_sticky(sticky, event) {
+        if let Some(entity) = world.get_entity_mut(replaced) {
+            entity.despawn();
+        }
+    }
+}
+
+/// What to do with an event after it has been intercepted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Intercept {
+    /// Pass the event on to the next interceptor, and eventually to the readers.
+    #[default]
+    Continue,
+    /// Despawn the event before any reader sees it.
+    Veto,
+}
+
+impl From<()> for Intercept {
+    fn from(_: ()) -> Self {
+        Intercept::Continue
+    }
+}
+
+impl From<bool> for Intercept {
+    /// `true` lets the event through, `false` vetoes it.
+    fn from(value: bool) -> Self {
+        match value {
+            true => Intercept::Continue,
+            false => Intercept::Veto,
+        }
+    }
+}
+
+pub type BoxedInterceptor = BoxedSystem<Entity, Intercept>;
+
+/// Systems that run for every event right after it's sent, before any reader sees it.
+///
+/// Interceptors take the event entity as input and are able to query the world, which means they can
+/// inspect and modify the event, veto it by returning [`Intercept::Veto`] or send more events.
+/// Events sent from an interceptor are intercepted as well, once the current event is done.
+///
+/// Events sent with [`Commands`] are intercepted as soon as the send command is applied, so the interceptors
+/// see the components of the sent bundle, but not the ones inserted by later commands.
+/// Events sent with [`send_event`] are queued until [`intercept_queued_events`] runs instead.
+/// Vetoing an event of an [`EventBatch`] cancels the whole batch.
+#[derive(Resource, Default)]
+pub struct EventInterceptors {
+    interceptors: Vec<(BoxedInterceptor, bool)>,
+    pending: VecDeque<Entity>,
+    /// Events sent with [`send_event`], waiting for [`intercept_queued_events`].
+    queued: Vec<Entity>,
+    running: bool,
+}
+
+impl EventInterceptors {
+    /// Add an interceptor after the existing ones.
+    pub fn add<O, M>(&mut self, interceptor: impl IntoSystem<Entity, O, M>)
+    where
+        O: Into<Intercept> + 'static,
+    {
+        self.extend([Self::box_interceptor(interceptor)]);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn box_interceptor<O, M>(interceptor: impl IntoSystem<Entity, O, M>) -> BoxedInterceptor
+    where
+        O: Into<Intercept> + 'static,
+    {
+        Box::new(IntoSystem::into_system(
+            interceptor.pipe(|In(intercept): In<O>| intercept.into()),
+        ))
+    }
+}
+
+impl Extend<BoxedInterceptor> for EventInterceptors {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = BoxedInterceptor>,
+    {
+        self.interceptors
+            .extend(iter.into_iter().map(|interceptor| (interceptor, false)));
+    }
+}
+
+fn intercept_events(world: &mut World, events: impl IntoIterator<Item = Entity>) {
+    let mut interceptors = world.resource_mut::<EventInterceptors>();
+    interceptors.pending.extend(events);
+    // events sent while intercepting are handled by the outermost call
+    if interceptors.running {
+        return;
+    }
+    interceptors.running = true;
+    let systems = std::mem::take(&mut interceptors.interceptors);
+    let mut guard = InterceptGuard { world, systems };
+    let InterceptGuard { world, systems } = &mut guard;
+
+    while let Some(event) = world
+        .resource_mut::<EventInterceptors>()
+        .pending
+        .pop_front()
+    {
+        let mut intercept = Intercept::Continue;
+        for (system, initialized) in systems.iter_mut() {
+            if world.get_entity(event).is_none() {
+                break;
+            }
+            if !*initialized {
+                system.initialize(world);
+                *initialized = true;
+            }
+            intercept = system.run(event, world);
+            system.apply_deferred(world);
+            if intercept == Intercept::Veto {
+                break;
+            }
+        }
+
+        match (intercept, world.get_entity_mut(event)) {
+            (Intercept::Continue, Some(_)) => push_event_unintercepted(world, event),
+            (Intercept::Veto, Some(_)) => {
+                trace!("event {event:?} was vetoed");
+                cancel_event(world, event);
+            }
+            (_, None) => {}
+        }
+    }
+}
+
+/// Puts the interceptors back once intercepting is done, even if an interceptor panics.
+struct InterceptGuard<'w> {
+    world: &'w mut World,
+    systems: Vec<(BoxedInterceptor, bool)>,
+}
+
+impl Drop for InterceptGuard<'_> {
+    fn drop(&mut self) {
+        let Some(mut interceptors) = self.world.get_resource_mut::<EventInterceptors>() else {
+            return;
+        };
+        // keep the interceptors that were added while intercepting
+        let mut systems = std::mem::take(&mut self.systems);
+        systems.append(&mut interceptors.interceptors);
+        interceptors.interceptors = systems;
+        interceptors.running = false;
+    }
+}
+
 pub trait SendEventExt {
     type Output<'a>
     where
         Self: 'a;
 
     /// Spawn an entity and push it to the `Events` resource. Returns the `EntityCommands` of the spawned event.
+    ///
+    /// Note that the event is despawned if it's vetoed by one of the [`EventInterceptors`].
+    /// On [`Commands`], the interceptors run when the event is spawned, before the components inserted
+    /// with the returned `EntityCommands`, so put everything the interceptors should see in the bundle.
     fn send_event(&mut self, event: impl Bundle) -> Self::Output<'_>;
 
     fn send_event_batch<I>(&mut self, iter: I)
     where
         I: IntoIterator + Send + Sync + 'static,
         I::Item: Bundle;
+
+    /// Send a [`Sticky`] event. The latest sticky event of each kind is kept around after it would
+    /// normally expire, so that readers added later will still receive it once.
+    ///
+    /// The kind of the event is the type of the event component, so other components like a target
+    /// can be added to the returned event without changing its kind.
+    fn send_sticky_event<E: Component>(&mut self, event: E) -> Self::Output<'_> {
+        self.send_event((event, Sticky::new::<E>()))
+    }
 }
 
 impl<'w, 's> SendEventExt for Commands<'w, 's> {
-    type Output<'a> = EntityCommands<'a> where Self: 'a;
+    type Output<'a>
+        = EntityCommands<'a>
+    where
+        Self: 'a;
 
-    fn send_event(&mut self, event: impl Bundle) -> EntityCommands {
+    fn send_event(&mut self, event: impl Bundle) -> EntityCommands<'_> {
         let entity = self.spawn_empty().id();
         self.add(move |world: &mut World| {
-            world.resource_mut::<EventEntities>().push(entity);
             world.entity_mut(entity).insert(event);
+            push_event(world, entity);
         });
         self.entity(entity)
     }
@@ -136,13 +690,144 @@ impl<'w, 's> SendEventExt for Commands<'w, 's> {
         I::Item: Bundle,
     {
         self.add(|world: &mut World| {
-            world.resource_scope::<EventEntities, _>(|world, mut events| {
-                events.extend(world.spawn_batch(iter));
-            });
+            let events: Vec<Entity> = world.spawn_batch(iter).collect();
+            push_events(world, events);
         });
     }
 }
 
+/// Groups events that were sent together with [`SendEventTransactionExt::send_event_transaction`].
+///
+/// Every event in the batch has the same `EventBatch` component.
+/// Cancelling one of the events with [`CancelEventExt::cancel_event`] cancels the whole batch.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventBatch {
+    events: Arc<[Entity]>,
+}
+
+impl EventBatch {
+    /// Returns the events in the batch, in the order they were sent.
+    pub fn events(&self) -> &[Entity] {
+        &self.events
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns `true` if none of the events in the batch have been despawned.
+    pub fn is_complete(&self, entities: &Entities) -> bool {
+        self.events.iter().all(|event| entities.contains(*event))
+    }
+}
+
+/// Sends a group of events as an [`EventBatch`], see [`SendEventTransactionExt::send_event_transaction`].
+pub struct EventTransaction<'a, 'w, 's> {
+    commands: &'a mut Commands<'w, 's>,
+    events: Vec<Entity>,
+}
+
+impl<'a, 'w, 's> EventTransaction<'a, 'w, 's> {
+    /// Add an event to the transaction. Returns the `EntityCommands` of the spawned event.
+    pub fn send(&mut self, event: impl Bundle) -> EntityCommands<'_> {
+        let entity = self.commands.spawn(event).id();
+        self.events.push(entity);
+        self.commands.entity(entity)
+    }
+
+    /// Returns the events that have been added to the transaction so far.
+    pub fn events(&self) -> &[Entity] {
+        &self.events
+    }
+}
+
+pub trait SendEventTransactionExt<'w, 's> {
+    /// Send a group of related events as a single [`EventBatch`].
+    ///
+    /// The events are pushed to the `EventEntities` resource at the same time, so readers will never observe a
+    /// partial batch. If one of the events is vetoed by an interceptor, the whole batch is cancelled.
+    ///
+    /// ```ignore
+    /// commands.send_event_transaction(|tx| {
+    ///     tx.send(RemoveItem(sword));
+    ///     tx.send(AddItem(sword));
+    ///     tx.send(GoldChanged(-10));
+    /// });
+    /// ```
+    fn send_event_transaction(
+        &mut self,
+        transaction: impl FnOnce(&mut EventTransaction<'_, 'w, 's>),
+    ) -> EventBatch;
+}
+
+impl<'w, 's> SendEventTransactionExt<'w, 's> for Commands<'w, 's> {
+    fn send_event_transaction(
+        &mut self,
+        transaction: impl FnOnce(&mut EventTransaction<'_, 'w, 's>),
+    ) -> EventBatch {
+        let mut tx = EventTransaction {
+            commands: self,
+            events: Vec::new(),
+        };
+        transaction(&mut tx);
+        let batch = EventBatch {
+            events: tx.events.into(),
+        };
+        let commit = batch.clone();
+        self.add(move |world: &mut World| {
+            for event in commit.events() {
+                world.entity_mut(*event).insert(commit.clone());
+            }
+            push_events(world, commit.events().iter().copied());
+        });
+        batch
+    }
+}
+
+/// Despawn an event. If the event is part of an [`EventBatch`], every event in the batch is despawned.
+pub fn cancel_event(world: &mut World, event: Entity) {
+    match world.get::<EventBatch>(event).cloned() {
+        Some(batch) => cancel_event_batch(world, &batch),
+        None => {
+            world.despawn(event);
+        }
+    }
+}
+
+fn cancel_event_batch(world: &mut World, batch: &EventBatch) {
+    trace!("cancelling event batch {:?}", batch.events());
+    for event in batch.events() {
+        if let Some(entity) = world.get_entity_mut(*event) {
+            entity.despawn();
+        }
+    }
+}
+
+pub trait CancelEventExt {
+    /// Despawn an event. If the event is part of an [`EventBatch`], every event in the batch is despawned.
+    fn cancel_event(&mut self, event: Entity) -> &mut Self;
+}
+
+impl<'w, 's> CancelEventExt for Commands<'w, 's> {
+    fn cancel_event(&mut self, event: Entity) -> &mut Self {
+        self.add(move |world: &mut World| cancel_event(world, event));
+        self
+    }
+}
+
+impl CancelEventExt for World {
+    fn cancel_event(&mut self, event: Entity) -> &mut Self {
+        cancel_event(self, event);
+        self
+    }
+}
+
 #[derive(Reflect, Debug, Default, Clone)]
 pub struct EventSequence {
     events: Vec<Entity>,
@@ -169,20 +854,98 @@ impl DerefMut for EventSequence {
     }
 }
 
+/// Marks an event as sticky.
+///
+/// The latest sticky event of each kind is kept alive after it would normally expire, and every
+/// reader will receive it once, even readers that were added after the event was sent.
+/// Sending a new sticky event of the same kind replaces the old one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sticky(TypeId);
+
+impl Sticky {
+    /// Returns the sticky kind of events with the event component `T`.
+    pub fn new<T: Component>() -> Self {
+        Self(TypeId::of::<T>())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickyEvent {
+    pub kind: Sticky,
+    pub entity: Entity,
+    event_count: usize,
+}
+
 #[derive(Resource, Reflect, Debug, Default, Clone)]
 pub struct EventEntities {
     events_a: EventSequence,
     events_b: EventSequence,
     event_count: usize,
+    /// The latest sticky event of each kind, ordered from oldest to newest.
+    #[reflect(ignore)]
+    sticky: Vec<StickyEvent>,
 }
 
 impl EventEntities {
+    /// Returns the total number of events that have been pushed to the `EventEntities` resource.
+    #[inline]
+    pub fn event_count(&self) -> usize {
+        self.event_count
+    }
+
     /// Push an event to the `EventEntities` resource.
     pub fn push(&mut self, event: Entity) {
         self.events_b.push(event);
         self.event_count += 1;
     }
 
+    /// Push a sticky event to the `EventEntities` resource, replacing the previous sticky event of the same kind.
+    ///
+    /// Returns the replaced event if it has already expired, in which case it should be despawned.
+    pub fn push_sticky(&mut self, kind: Sticky, event: Entity) -> Option<Entity> {
+        let replaced = self
+            .sticky
+            .iter()
+            .position(|sticky| sticky.kind == kind)
+            .map(|index| self.sticky.remove(index))
+            .filter(|sticky| sticky.event_count < self.oldest_event_count())
+            .map(|sticky| sticky.entity);
+        self.sticky.push(StickyEvent {
+            kind,
+            entity: event,
+            event_count: self.event_count,
+        });
+        self.push(event);
+        replaced
+    }
+
+    /// Returns the latest sticky event of each kind, including the ones that have not expired yet.
+    pub fn sticky(&self) -> &[StickyEvent] {
+        &self.sticky
+    }
+
+    /// Returns the sticky events that have expired, but are kept alive since they have not been replaced yet.
+    pub fn retained_sticky(&self) -> &[StickyEvent] {
+        let oldest_event_count = self.oldest_event_count();
+        let end = self
+            .sticky
+            .partition_point(|sticky| sticky.event_count < oldest_event_count);
+        &self.sticky[..end]
+    }
+
+    /// Returns the retained sticky events that a reader at `last_event_count` has not read yet.
+    fn unread_sticky(&self, last_event_count: usize) -> &[StickyEvent] {
+        let retained = self.retained_sticky();
+        let start = retained.partition_point(|sticky| sticky.event_count < last_event_count);
+        &retained[start..]
+    }
+
+    /// Remove all sticky events, returning the ones that have expired so they can be despawned.
+    pub fn drain_sticky(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        let end = self.retained_sticky().len();
+        self.sticky.drain(..).take(end).map(|sticky| sticky.entity)
+    }
+
     pub fn into_inner(self) -> (EventSequence, EventSequence) {
         (self.events_a, self.events_b)
     }
@@ -210,15 +973,21 @@ impl EventEntities {
             .min(self.events_b.start_event_count)
     }
 
+    /// Swap the event buffers and drain the expired events.
+    ///
+    /// The latest [`Sticky`] event of each kind is retained and not returned by the iterator.
     pub fn update_drain(&mut self) -> impl Iterator<Item = Entity> + '_ {
         std::mem::swap(&mut self.events_a, &mut self.events_b);
-        let iter = self.events_b.events.drain(..);
         self.events_b.start_event_count = self.event_count;
         debug_assert_eq!(
             self.events_a.start_event_count + self.events_a.len(),
             self.events_b.start_event_count
         );
-        iter
+        let sticky = &self.sticky;
+        self.events_b
+            .events
+            .drain(..)
+            .filter(move |event| !sticky.iter().any(|sticky| sticky.entity == *event))
     }
 
     pub fn update(&mut self) {
@@ -251,28 +1020,19 @@ impl Extend<Entity> for EventEntities {
         I: IntoIterator<Item = Entity>,
     {
         let mut event_count = self.event_count;
-        let events = iter.into_iter().map(|event| {
+        let events = iter.into_iter().inspect(|_| {
             event_count += 1;
-            event
         });
         self.events_b.extend(events);
         self.event_count = event_count;
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct EventEntityReader {
     last_event_count: usize,
 }
 
-impl Default for EventEntityReader {
-    fn default() -> Self {
-        Self {
-            last_event_count: 0,
-        }
-    }
-}
-
 impl EventEntityReader {
     pub fn read<'a>(&'a mut self, events: &'a EventEntities) -> EntityEventIterator<'a> {
         EntityEventIterator::new(self, events)
@@ -290,10 +1050,12 @@ impl EventEntityReader {
     }
 
     pub fn len(&self, events: &EventEntities) -> usize {
-        events
+        let sticky = events.unread_sticky(self.last_event_count).len();
+        let buffered = events
             .event_count
             .saturating_sub(self.last_event_count)
-            .min(events.len())
+            .min(events.len());
+        sticky + buffered
     }
 }
 
@@ -325,11 +1087,84 @@ pub struct EntityEventReader<'w, 's> {
 }
 
 impl<'w, 's> EntityEventReader<'w, 's> {
-    pub fn read(&mut self) -> EntityEventIterator {
+    pub fn read(&mut self) -> EntityEventIterator<'_> {
         self.reader.read(&self.events)
     }
 }
 
+/// Reads events one [`EventBatch`] at a time.
+///
+/// Batches where one of the events has been despawned are skipped entirely.
+/// Events that were not sent as part of a batch are read as a batch of one.
+#[derive(SystemParam)]
+pub struct EventBatchReader<'w, 's, D, F = ()>
+where
+    D: ReadOnlyQueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    reader: Local<'s, EventEntityReader>,
+    events: Res<'w, EventEntities>,
+    entities: &'w Entities,
+    batches: Query<'w, 's, &'static EventBatch>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, D, F> EventBatchReader<'w, 's, D, F>
+where
+    D: ReadOnlyQueryData,
+    F: QueryFilter,
+{
+    /// Returns an iterator over the unread batches.
+    /// Each item contains the events in the batch that match the query.
+    pub fn read<'a>(&'a mut self) -> EventBatchIterator<'w, 's, 'a, D, F> {
+        EventBatchIterator {
+            inner: self.reader.read(&self.events),
+            entities: self.entities,
+            batches: &self.batches,
+            query: &self.query,
+        }
+    }
+}
+
+pub struct EventBatchIterator<'w, 's, 'a, D: ReadOnlyQueryData, F: QueryFilter> {
+    inner: EntityEventIterator<'a>,
+    entities: &'a Entities,
+    batches: &'a Query<'w, 's, &'static EventBatch>,
+    query: &'a Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, 'a, D: ReadOnlyQueryData, F: QueryFilter> Iterator
+    for EventBatchIterator<'w, 's, 'a, D, F>
+{
+    type Item = Vec<D::Item<'w>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let event = self.inner.next()?;
+            let Ok(batch) = self.batches.get(event) else {
+                match self.query.get_inner(event) {
+                    Ok(item) => return Some(vec![item]),
+                    Err(_) => continue,
+                }
+            };
+
+            // read the whole batch at its first event, and skip its other events wherever they are
+            if batch.events().first() != Some(&event) || !batch.is_complete(self.entities) {
+                continue;
+            }
+
+            let items: Vec<_> = batch
+                .events()
+                .iter()
+                .filter_map(|event| self.query.get_inner(*event).ok())
+                .collect();
+            if !items.is_empty() {
+                return Some(items);
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct QueryEventIterator<'w, 's, 'a, D: ReadOnlyQueryData, F: QueryFilter> {
     inner: EntityEventIterator<'a>,
@@ -342,7 +1177,7 @@ impl<'w, 's, 'a, D: ReadOnlyQueryData, F: QueryFilter> Iterator
     type Item = D::Item<'w>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(entity) = self.inner.next() {
+        for entity in self.inner.by_ref() {
             if let Ok(inner) = self.query.get_inner(entity) {
                 return Some(inner);
             }
@@ -354,12 +1189,15 @@ impl<'w, 's, 'a, D: ReadOnlyQueryData, F: QueryFilter> Iterator
 #[derive(Debug)]
 pub struct EntityEventIterator<'a> {
     reader: &'a mut EventEntityReader,
+    sticky: Iter<'a, StickyEvent>,
     chain: Chain<Iter<'a, Entity>, Iter<'a, Entity>>,
+    event_count: usize,
     unread: usize,
 }
 
 impl<'a> EntityEventIterator<'a> {
     pub fn new(reader: &'a mut EventEntityReader, events: &'a EventEntities) -> Self {
+        let sticky = events.unread_sticky(reader.last_event_count);
         let a_index = reader
             .last_event_count
             .saturating_sub(events.events_a.start_event_count);
@@ -369,16 +1207,20 @@ impl<'a> EntityEventIterator<'a> {
         let a = events.events_a.get(a_index..).unwrap_or_default();
         let b = events.events_b.get(b_index..).unwrap_or_default();
 
-        let unread_count = a.len() + b.len();
+        let unread_count = sticky.len() + a.len() + b.len();
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
-        reader.last_event_count = events.event_count - unread_count;
-        // Iterate the oldest first, then the newer events
+        if sticky.is_empty() {
+            reader.last_event_count = events.event_count - unread_count;
+        }
+        // Iterate the retained sticky events first, then the oldest, then the newer events
         let chain = a.iter().chain(b.iter());
 
         Self {
             reader,
+            sticky: sticky.iter(),
             chain,
+            event_count: events.event_count,
             unread: unread_count,
         }
     }
@@ -388,10 +1230,15 @@ impl<'a> Iterator for EntityEventIterator<'a> {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sticky) = self.sticky.next() {
+            self.reader.last_event_count = sticky.event_count + 1;
+            self.unread -= 1;
+            return Some(sticky.entity);
+        }
         match self.chain.next() {
             Some(entity) => {
-                self.reader.last_event_count += 1;
                 self.unread -= 1;
+                self.reader.last_event_count = self.event_count - self.unread;
                 Some(*entity)
             }
             None => None,
@@ -399,11 +1246,11 @@ impl<'a> Iterator for EntityEventIterator<'a> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.chain.size_hint()
+        (self.unread, Some(self.unread))
     }
 
     fn count(self) -> usize {
-        self.reader.last_event_count += self.unread;
+        self.reader.last_event_count = self.event_count;
         self.unread
     }
 
@@ -411,18 +1258,30 @@ impl<'a> Iterator for EntityEventIterator<'a> {
     where
         Self: Sized,
     {
-        let entity = self.chain.last()?;
-        self.reader.last_event_count += self.unread;
-        Some(*entity)
+        let entity = match self.chain.last() {
+            Some(entity) => *entity,
+            None => self.sticky.last()?.entity,
+        };
+        self.reader.last_event_count = self.event_count;
+        Some(entity)
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.sticky.len() {
+            let sticky = self.sticky.nth(n)?;
+            self.reader.last_event_count = sticky.event_count + 1;
+            self.unread -= n + 1;
+            return Some(sticky.entity);
+        }
+        let n = n - self.sticky.len();
+        self.unread -= self.sticky.len();
+        self.sticky = [].iter();
         if let Some(entity) = self.chain.nth(n) {
-            self.reader.last_event_count += n + 1;
             self.unread -= n + 1;
+            self.reader.last_event_count = self.event_count - self.unread;
             Some(*entity)
         } else {
-            self.reader.last_event_count += self.unread;
+            self.reader.last_event_count = self.event_count;
             self.unread = 0;
             None
         }
@@ -434,3 +1293,380 @@ impl<'a> ExactSizeIterator for EntityEventIterator<'a> {
         self.unread
     }
 }
+
+#[test]
+fn test_sticky_events() {
+    #[derive(Component)]
+    struct LevelLoaded(u32);
+
+    #[derive(Component)]
+    struct Reloaded;
+
+    fn read(world: &mut World, reader: &mut EventEntityReader) -> Vec<u32> {
+        let events = world.resource::<EventEntities>();
+        reader
+            .read(events)
+            .filter_map(|event| world.get::<LevelLoaded>(event).map(|level| level.0))
+            .collect()
+    }
+
+    let mut world = World::new();
+    world.init_resource::<EventEntities>();
+
+    let mut early_reader = EventEntityReader::default();
+    send_sticky_event(&mut world, LevelLoaded(1));
+    assert_eq!(read(&mut world, &mut early_reader), [1]);
+
+    // let the event expire
+    for _ in 0..2 {
+        world.resource_scope::<EventEntities, _>(|world, mut events| {
+            for entity in events.update_drain() {
+                world.despawn(entity);
+            }
+        });
+    }
+
+    // a reader added later still receives the sticky event once
+    let mut late_reader = EventEntityReader::default();
+    assert_eq!(read(&mut world, &mut late_reader), [1]);
+    assert_eq!(read(&mut world, &mut late_reader), []);
+    assert_eq!(read(&mut world, &mut early_reader), []);
+
+    // a new sticky event replaces the old one
+    send_sticky_event(&mut world, LevelLoaded(2));
+    assert_eq!(world.resource::<EventEntities>().sticky().len(), 1);
+    assert_eq!(read(&mut world, &mut late_reader), [2]);
+    assert_eq!(read(&mut world, &mut early_reader), [2]);
+    assert_eq!(read(&mut world, &mut EventEntityReader::default()), [2]);
+
+    // the kind is the event component, regardless of the other components of the event
+    send_event(
+        &mut world,
+        (LevelLoaded(3), Reloaded, Sticky::new::<LevelLoaded>()),
+    );
+    assert_eq!(world.resource::<EventEntities>().sticky().len(), 1);
+    assert_eq!(read(&mut world, &mut late_reader), [3]);
+}
+
+#[test]
+fn test_event_pipeline() {
+    #[derive(Component)]
+    struct Attack(u32);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(usize, u32)>);
+
+    fn block(
+        mut commands: Commands,
+        mut events: QueryEventReader<(Entity, &Attack)>,
+        mut log: ResMut<Log>,
+    ) {
+        for (event, attack) in events.read() {
+            log.0.push((0, attack.0));
+            if attack.0 == 0 {
+                commands.entity(event).despawn();
+            }
+        }
+    }
+
+    fn apply(mut events: QueryEventReader<&Attack>, mut log: ResMut<Log>) {
+        for attack in events.read() {
+            log.0.push((1, attack.0));
+        }
+    }
+
+    let mut app = App::new();
+    app.init_resource::<EventEntities>().init_resource::<Log>();
+    app.add_event_pipeline_to::<Attack>(Update)
+        .stage(block)
+        .stage(apply);
+
+    for damage in [1, 0, 2] {
+        send_event(&mut app.world, Attack(damage));
+    }
+    app.update();
+
+    assert_eq!(
+        app.world.resource::<Log>().0,
+        [(0, 1), (0, 0), (0, 2), (1, 1), (1, 2)]
+    );
+}
+
+#[test]
+fn test_event_interceptors() {
+    use bevy_ecs::system::{CommandQueue, RunSystemOnce};
+
+    #[derive(Component)]
+    struct Attack(u32);
+
+    #[derive(Component)]
+    struct Tagged;
+
+    #[derive(Component)]
+    struct Echo;
+
+    #[derive(Component)]
+    struct Blocked;
+
+    fn god_mode(In(event): In<Entity>, attacks: Query<(&Attack, Has<Blocked>)>) -> Intercept {
+        match attacks.get(event) {
+            Ok((Attack(0), _)) | Ok((_, true)) => Intercept::Veto,
+            _ => Intercept::Continue,
+        }
+    }
+
+    fn tag(In(event): In<Entity>, mut commands: Commands, echoes: Query<(), With<Echo>>) {
+        commands.entity(event).insert(Tagged);
+        if !echoes.contains(event) {
+            commands.send_event(Echo);
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(
+        EventPlugin::default()
+            .with_interceptor(god_mode)
+            .with_interceptor(tag),
+    );
+
+    for damage in [1, 0, 2] {
+        try_send_event(&mut app.world, Attack(damage));
+    }
+
+    let events: Vec<Entity> = app.world.resource::<EventEntities>().iter().collect();
+    let mut query = app
+        .world
+        .query::<(Option<&Attack>, Has<Echo>, Has<Tagged>)>();
+    let events: Vec<_> = events
+        .into_iter()
+        .map(|event| {
+            let (attack, echo, tagged) = query.get(&app.world, event).unwrap();
+            (attack.map(|attack| attack.0), echo, tagged)
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            (Some(1), false, true),
+            (None, true, true),
+            (Some(2), false, true),
+            (None, true, true)
+        ]
+    );
+
+    // events sent with commands are intercepted when the send command is applied
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &app.world);
+    let blocked = commands.send_event((Attack(3), Blocked)).id();
+    let vetoed = commands.send_event((Attack(0), Blocked)).id();
+    let sent = commands.send_event(Attack(4)).id();
+    queue.apply(&mut app.world);
+    assert!(app.world.get_entity(blocked).is_none());
+    assert!(app.world.get_entity(vetoed).is_none());
+    assert!(app.world.get::<Tagged>(sent).is_some());
+    assert_eq!(app.world.resource::<EventEntities>().len(), 6);
+
+    // events sent to the world are queued until they're intercepted, but still count as events
+    let blocked = send_event(&mut app.world, Attack(5)).insert(Blocked).id();
+    app.world.resource_mut::<EventEntities>().update();
+    app.world.resource_mut::<EventEntities>().update();
+    assert!(app.world.run_system_once(any_events));
+    intercept_queued_events(&mut app.world);
+    assert!(app.world.get_entity(blocked).is_none());
+    assert!(!app.world.run_system_once(any_events));
+}
+
+#[test]
+fn test_interceptor_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[derive(Component)]
+    struct Attack(u32);
+
+    fn fragile(In(event): In<Entity>, attacks: Query<&Attack>) -> bool {
+        match attacks.get(event) {
+            Ok(Attack(0)) => panic!("attack without damage"),
+            _ => false,
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(EventPlugin::default().with_interceptor(fragile));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        try_send_event(&mut app.world, Attack(0));
+    }));
+    assert!(result.is_err());
+
+    // the interceptors are still in place after the panic
+    let interceptors = app.world.resource::<EventInterceptors>();
+    assert_eq!(interceptors.len(), 1);
+    assert!(!interceptors.running);
+    assert!(try_send_event(&mut app.world, Attack(1)).is_none());
+    assert!(app.world.resource::<EventEntities>().is_empty());
+}
+
+#[test]
+fn test_event_transactions() {
+    use bevy_ecs::system::{CommandQueue, RunSystemOnce};
+
+    #[derive(Component)]
+    struct Trade(u32);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<Vec<u32>>);
+
+    fn read_batches(mut reader: EventBatchReader<&Trade>, mut log: ResMut<Log>) {
+        for batch in reader.read() {
+            log.0.push(batch.into_iter().map(|trade| trade.0).collect());
+        }
+    }
+
+    fn send_trade(world: &mut World, trade: [u32; 3]) -> EventBatch {
+        let mut queue = CommandQueue::default();
+        let batch = Commands::new(&mut queue, world).send_event_transaction(|tx| {
+            for n in trade {
+                tx.send(Trade(n));
+            }
+        });
+        queue.apply(world);
+        batch
+    }
+
+    let mut world = World::new();
+    world.init_resource::<EventEntities>();
+    world.init_resource::<Log>();
+
+    send_trade(&mut world, [1, 2, 3]);
+    send_event(&mut world, Trade(4));
+    // a batch missing an event is never read
+    let partial = send_trade(&mut world, [5, 6, 7]);
+    world.despawn(partial.events()[1]);
+    // cancelling an event cancels the whole batch
+    let cancelled = send_trade(&mut world, [8, 9, 10]);
+    world.cancel_event(cancelled.events()[2]);
+    assert!(cancelled
+        .events()
+        .iter()
+        .all(|event| world.get_entity(*event).is_none()));
+
+    world.run_system_once(read_batches);
+    assert_eq!(world.resource::<Log>().0, [vec![1, 2, 3], vec![4]]);
+}
+
+#[test]
+fn test_event_transaction_interceptors() {
+    use bevy_ecs::system::{CommandQueue, RunSystemOnce};
+
+    #[derive(Component)]
+    struct Trade(u32);
+
+    #[derive(Component)]
+    struct Audit(u32);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<Vec<(Option<u32>, Option<u32>)>>);
+
+    // pushes an unrelated event right away, in between the events of the batch
+    fn audit(In(event): In<Entity>, mut commands: Commands, trades: Query<&Trade>) {
+        if let Ok(trade) = trades.get(event) {
+            let audit = commands.spawn(Audit(trade.0)).id();
+            commands.add(move |world: &mut World| {
+                world.resource_mut::<EventEntities>().push(audit);
+            });
+        }
+    }
+
+    fn read_batches(
+        mut reader: EventBatchReader<(Option<&Trade>, Option<&Audit>)>,
+        mut log: ResMut<Log>,
+    ) {
+        for batch in reader.read() {
+            let batch = batch
+                .into_iter()
+                .map(|(t, a)| (t.map(|t| t.0), a.map(|a| a.0)));
+            log.0.push(batch.collect());
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(EventPlugin::default().with_interceptor(audit))
+        .init_resource::<Log>();
+
+    let mut queue = CommandQueue::default();
+    Commands::new(&mut queue, &app.world).send_event_transaction(|tx| {
+        for n in [1, 2, 3] {
+            tx.send(Trade(n));
+        }
+    });
+    queue.apply(&mut app.world);
+
+    app.world.run_system_once(read_batches);
+    assert_eq!(
+        app.world.resource::<Log>().0,
+        [
+            vec![(None, Some(1))],
+            vec![(Some(1), None), (Some(2), None), (Some(3), None)],
+            vec![(None, Some(2))],
+            vec![(None, Some(3))],
+        ]
+    );
+}
+
+#[test]
+fn test_event_cascade() {
+    #[derive(Component)]
+    struct Attack;
+
+    #[derive(Component)]
+    struct Kill;
+
+    #[derive(Component)]
+    struct DropLoot;
+
+    #[derive(Resource, Default)]
+    struct Loot(usize);
+
+    // the systems are added in reverse order, so every step has to wait for the next iteration
+    fn drop_loot(mut events: QueryEventReader<(), With<DropLoot>>, mut loot: ResMut<Loot>) {
+        loot.0 += events.read().count();
+    }
+
+    fn kill(mut commands: Commands, mut events: QueryEventReader<(), With<Kill>>) {
+        for _ in events.read() {
+            commands.send_event(DropLoot);
+        }
+    }
+
+    fn attack(mut commands: Commands, mut events: QueryEventReader<(), With<Attack>>) {
+        for _ in events.read() {
+            commands.send_event(Kill);
+        }
+    }
+
+    let mut app = App::new();
+    app.init_resource::<EventEntities>()
+        .init_resource::<Loot>()
+        .add_plugins(EventCascadePlugin::new(Update))
+        .add_systems(EventCascade, (drop_loot, kill, attack).chain());
+
+    send_event(&mut app.world, Attack);
+    app.update();
+    assert_eq!(app.world.resource::<Loot>().0, 1);
+
+    // the cascade stops after the max number of iterations
+    fn forever(mut commands: Commands, mut events: QueryEventReader<(), With<Kill>>) {
+        for _ in events.read() {
+            commands.send_event(Kill);
+        }
+    }
+
+    let mut app = App::new();
+    app.init_resource::<EventEntities>()
+        .add_plugins(EventCascadePlugin::new(Update).with_max_iterations(4))
+        .add_systems(EventCascade, forever);
+
+    send_event(&mut app.world, Kill);
+    app.update();
+    assert_eq!(app.world.resource::<EventEntities>().event_count(), 5);
+}
@@ -0,0 +1,134 @@
+use std::{any::TypeId, sync::Arc};
+
+use bevy_ecs::{component::ComponentId, world::EntityRef};
+
+use crate::{CallbackIdent, Listenable, Target};
+
+/// Matches every event with a [`Target`] or [`Targets`](crate::Targets).
+///
+/// Useful for debug and analytics listeners that want to see all targeted events.
+pub struct AnyEvent;
+
+impl Listenable for AnyEvent {
+    fn entity_contains(entity: EntityRef) -> bool {
+        Target::entity_contains(entity)
+    }
+}
+
+/// Matches every event, the base of callbacks created with [`CallbackIdent::from_component_ids`].
+///
+/// Use it with [`On::with_component_ids`](crate::On::with_component_ids) to listen to events with components only known at runtime.
+pub struct DynamicEvent;
+
+impl Listenable for DynamicEvent {
+    fn entity_contains(_: EntityRef) -> bool {
+        true
+    }
+}
+
+/// Identifies the callbacks that match the same events.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum CallbackKey {
+    /// A [`Listenable`] type.
+    Type(TypeId),
+    /// A [`Listenable`] type, and the components an event needs in addition, sorted by id.
+    Dynamic(TypeId, Arc<[ComponentId]>),
+}
+
+impl CallbackIdent {
+    /// Match the events with all of these components, for components only known at runtime.
+    ///
+    /// ```ignore
+    /// let id = world.components().get_id(type_id).unwrap();
+    /// let ident = CallbackIdent::from_component_ids([id]);
+    /// ```
+    pub fn from_component_ids(ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        Self::new::<DynamicEvent>().with_component_ids(ids)
+    }
+
+    /// Require the events to also have all of these components.
+    pub fn with_component_ids(mut self, ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        let mut ids: Vec<ComponentId> = self.component_ids().iter().copied().chain(ids).collect();
+        if ids.is_empty() {
+            return self;
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        self.key = CallbackKey::Dynamic(self.type_id(), ids.into());
+        self
+    }
+
+    /// Returns the components required in addition to the [`Listenable`] type.
+    pub fn component_ids(&self) -> &[ComponentId] {
+        match &self.key {
+            CallbackKey::Type(_) => &[],
+            CallbackKey::Dynamic(_, ids) => ids,
+        }
+    }
+}
+
+// this tests if `AnyEvent` and callbacks with component ids match the right events
+#[test]
+fn test_dynamic_callbacks() {
+    use crate::*;
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Click;
+
+    #[derive(Component)]
+    struct Hover;
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<(&'static str, Entity)>);
+
+    fn any(input: Listener, mut seen: ResMut<Seen>) {
+        seen.0.push(("any", input.id()));
+    }
+
+    fn clicked(input: Listener, mut seen: ResMut<Seen>) {
+        seen.0.push(("click", input.id()));
+    }
+
+    fn button_clicked(input: Listener, mut seen: ResMut<Seen>) {
+        seen.0.push(("button", input.id()));
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Seen>();
+
+    let click = world.init_component::<Click>();
+    let button = world.spawn_empty().id();
+    world.add_callback::<AnyEvent, _>(any);
+    world.add_callback(On::<DynamicEvent>::run(clicked).with_component_ids([click]));
+    world
+        .entity_mut(button)
+        .add_callback(On::<DynamicEvent>::run(button_clicked).with_component_ids([click]));
+
+    let a = send_event(&mut world, (Click, Target(button))).id();
+    let b = send_event(&mut world, (Hover, Target(button))).id();
+    let c = send_event(&mut world, Click).id();
+    schedule.run(&mut world);
+    let mut seen = mem::take(&mut world.resource_mut::<Seen>().0);
+    seen.sort();
+    let mut expected = vec![
+        ("any", a),
+        ("any", b),
+        ("button", a),
+        ("click", a),
+        ("click", c),
+    ];
+    expected.sort();
+    assert_eq!(seen, expected);
+
+    // idents with the same components share a key, regardless of their order
+    let hover = world.init_component::<Hover>();
+    assert_eq!(
+        CallbackIdent::from_component_ids([click, hover]).key(),
+        CallbackIdent::from_component_ids([hover, click, hover]).key()
+    );
+    assert_ne!(
+        CallbackIdent::from_component_ids([click]).key(),
+        CallbackIdent::new::<DynamicEvent>().key()
+    );
+}
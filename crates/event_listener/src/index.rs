@@ -0,0 +1,298 @@
+use std::cmp::Reverse;
+
+use bevy_ecs::{prelude::*, system::SystemState, world::EntityRef};
+use bevy_hierarchy::Parent;
+use bevy_utils::{smallvec::SmallVec, HashMap};
+
+use crate::{CallbackId, CallbackIdent, CallbackPriority, EventCallbacks, Phase, Subscription};
+
+/// Index of the callbacks, keyed by the entity they are listening to and by [`Listenable`](crate::Listenable) type.
+///
+/// This is kept up to date by [`update_callback_index`], and by the [`AddCallbackExt`](crate::AddCallbackExt) methods.
+#[derive(Resource, Default)]
+pub struct CallbackIndex {
+    global: Vec<IndexedCallbacks>,
+    entities: HashMap<Entity, Vec<IndexedCallbacks>>,
+    /// The entity each callback is listening to, `None` for global callbacks, its priority and its sequence.
+    owners: HashMap<CallbackId, (Option<Entity>, CallbackPriority, u64)>,
+    /// Incremented for every inserted callback, to order callbacks with the same priority.
+    sequence: u64,
+    /// Incremented for every callback stored in [`EventCallbacks`], to give it a unique [`CallbackId::Stored`].
+    stored: u64,
+}
+
+struct IndexedCallbacks {
+    ident: CallbackIdent,
+    callbacks: Vec<IndexedCallback>,
+}
+
+struct IndexedCallback {
+    id: CallbackId,
+    priority: CallbackPriority,
+    sequence: u64,
+}
+
+impl IndexedCallback {
+    /// Callbacks with a higher priority run first, then the ones that were added first.
+    fn order(&self) -> (Reverse<CallbackPriority>, u64) {
+        (Reverse(self.priority), self.sequence)
+    }
+}
+
+impl CallbackIndex {
+    /// Add a callback to the index. `owner` is the entity the callback is listening to, or `None` for global callbacks.
+    pub fn insert(
+        &mut self,
+        callback: CallbackId,
+        owner: Option<Entity>,
+        ident: CallbackIdent,
+        priority: CallbackPriority,
+    ) {
+        // re-indexed callbacks keep their sequence, so they stay in the order they were added
+        let sequence = match self.owners.get(&callback) {
+            Some(&(o, p, _)) if (o, p) == (owner, priority) => return,
+            Some(&(_, _, sequence)) => {
+                self.remove(callback);
+                sequence
+            }
+            None => {
+                self.sequence += 1;
+                self.sequence - 1
+            }
+        };
+        self.owners.insert(callback, (owner, priority, sequence));
+        let callback = IndexedCallback {
+            id: callback,
+            priority,
+            sequence,
+        };
+        let groups = match owner {
+            Some(owner) => self.entities.entry(owner).or_default(),
+            None => &mut self.global,
+        };
+        match groups
+            .iter_mut()
+            .find(|group| group.ident.key == ident.key && group.ident.role() == ident.role())
+        {
+            Some(group) => {
+                let i = group
+                    .callbacks
+                    .partition_point(|c| c.order() < callback.order());
+                group.callbacks.insert(i, callback);
+            }
+            None => groups.push(IndexedCallbacks {
+                ident,
+                callbacks: vec![callback],
+            }),
+        }
+    }
+
+    /// Remove a callback from the index.
+    pub fn remove(&mut self, callback: CallbackId) {
+        let Some((owner, _, _)) = self.owners.remove(&callback) else {
+            return;
+        };
+        let groups = match owner {
+            Some(owner) => match self.entities.get_mut(&owner) {
+                Some(groups) => groups,
+                None => return,
+            },
+            None => &mut self.global,
+        };
+        for group in groups.iter_mut() {
+            group.callbacks.retain(|c| c.id != callback);
+        }
+        groups.retain(|group| !group.callbacks.is_empty());
+        if let Some(owner) = owner.filter(|_| groups.is_empty()) {
+            self.entities.remove(&owner);
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, callback: CallbackId) -> bool {
+        self.owners.contains_key(&callback)
+    }
+
+    /// Returns the entity the callback is listening to, `None` for global callbacks and callbacks not in the index.
+    pub fn owner(&self, callback: CallbackId) -> Option<Entity> {
+        self.owners.get(&callback).and_then(|&(owner, _, _)| owner)
+    }
+
+    /// Returns the callbacks listening to `owner`.
+    pub(crate) fn owned(&self, owner: Entity) -> impl Iterator<Item = CallbackId> + '_ {
+        self.entities
+            .get(&owner)
+            .into_iter()
+            .flatten()
+            .flat_map(|group| &group.callbacks)
+            .map(|c| c.id)
+    }
+
+    /// Allocate the id of a callback stored in [`EventCallbacks`].
+    pub(crate) fn allocate_stored(&mut self) -> CallbackId {
+        self.stored += 1;
+        CallbackId::Stored(self.stored - 1)
+    }
+
+    /// Returns the number of callbacks in the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collect the callbacks listening to `target` and, if `global` is `true`, the global callbacks,
+    /// that match the event in the given phase.
+    ///
+    /// In the [`Phase::Target`] phase, the capture callbacks are collected before the bubble callbacks.
+    /// The callbacks of `target` and the global callbacks are each ordered by their [`CallbackPriority`].
+    pub fn matching(
+        &self,
+        target: Option<Entity>,
+        event: EntityRef,
+        phase: Phase,
+        global: bool,
+        out: &mut Vec<CallbackId>,
+    ) {
+        // the (capture, source) roles of the callbacks running in this phase
+        let roles: &[(bool, bool)] = match phase {
+            Phase::Capture => &[(true, false)],
+            Phase::Target => &[(true, false), (false, false)],
+            Phase::Bubble => &[(false, false)],
+            Phase::Source => &[(false, true)],
+        };
+        let entity = target.and_then(|target| self.entities.get(&target));
+        let mut matching: SmallVec<[&IndexedCallback; 8]> = SmallVec::new();
+        for &role in roles {
+            for groups in entity.into_iter().chain(global.then_some(&self.global)) {
+                for group in groups {
+                    if group.ident.role() == role && group.ident.entity_contains(event) {
+                        matching.extend(&group.callbacks);
+                    }
+                }
+                matching.sort_unstable_by_key(|c| c.order());
+                out.extend(matching.drain(..).map(|c| c.id));
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_callback_index(
+    world: &mut World,
+    state: &mut SystemState<(
+        ResMut<CallbackIndex>,
+        Query<(
+            &CallbackIdent,
+            Option<&Parent>,
+            Option<&Subscription>,
+            Option<&CallbackPriority>,
+        )>,
+        Query<
+            Entity,
+            (
+                With<CallbackIdent>,
+                Or<(
+                    Added<CallbackIdent>,
+                    Changed<Parent>,
+                    Changed<CallbackPriority>,
+                )>,
+            ),
+        >,
+        RemovedComponents<CallbackIdent>,
+        RemovedComponents<Parent>,
+        RemovedComponents<CallbackPriority>,
+        Query<(Entity, &EventCallbacks), Changed<EventCallbacks>>,
+    )>,
+) {
+    world.init_resource::<CallbackIndex>();
+    let (
+        mut index,
+        callbacks,
+        changed,
+        mut removed,
+        mut removed_parents,
+        mut removed_priorities,
+        stored,
+    ) = state.get_mut(world);
+    for callback in removed.read() {
+        index.remove(CallbackId::Entity(callback));
+    }
+    // the priority of stored callbacks may have been changed with `EventCallbacks::get_mut`
+    for (owner, callbacks) in &stored {
+        for callback in callbacks {
+            index.insert(
+                callback.id(),
+                Some(owner),
+                callback.ident.clone(),
+                callback.priority,
+            );
+        }
+    }
+    let changed = changed
+        .iter()
+        .chain(removed_parents.read())
+        .chain(removed_priorities.read());
+    for callback in changed {
+        if let Ok((ident, parent, subscription, priority)) = callbacks.get(callback) {
+            let owner = match subscription {
+                Some(subscription) => Some(subscription.publisher),
+                None => parent.map(|p| p.get()),
+            };
+            let priority = priority.copied().unwrap_or_default();
+            index.insert(CallbackId::Entity(callback), owner, ident.clone(), priority);
+        }
+    }
+}
+
+// this tests if the callback index is updated when callbacks are added, moved or removed
+#[test]
+fn test_callback_index() {
+    use crate::*;
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    fn count(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Count>();
+
+    let a = world.spawn_empty().id();
+    let b = world.spawn_empty().id();
+    let callback = world
+        .entity_mut(a)
+        .add_callback::<TestEvent, _>(count)
+        .entity()
+        .unwrap();
+    // spawned without `AddCallbackExt`, this has to be picked up by `update_callback_index`
+    world
+        .spawn({
+            let on = On::<TestEvent>::run(count);
+            (on.ident, on.system)
+        })
+        .set_parent(b);
+
+    send_event(&mut world, (TestEvent, Target(a)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<CallbackIndex>().len(), 2);
+    assert_eq!(world.resource::<Count>().0, 1);
+
+    world.entity_mut(callback).set_parent(b);
+    send_event(&mut world, (TestEvent, Target(b)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Count>().0, 3);
+
+    world.despawn(callback);
+    send_event(&mut world, (TestEvent, Target(b)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<CallbackIndex>().len(), 1);
+    assert_eq!(world.resource::<Count>().0, 4);
+}
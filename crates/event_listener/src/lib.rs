@@ -1,34 +1,60 @@
 use std::{
+    any::TypeId,
     borrow::Cow,
     marker::PhantomData,
     mem,
     panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use bevy_app::{App, Plugin, PreUpdate};
 use bevy_ecs::{
     all_tuples,
-    entity::Entities,
+    component::ComponentId,
     prelude::*,
     query::{QueryData, QueryFilter, QueryItem, ROQueryItem},
-    schedule::{IntoSystemConfigs, ScheduleLabel, SystemConfigs},
-    system::{BoxedSystem, CommandQueue, EntityCommands, IntoSystem, SystemParam},
+    schedule::{BoxedCondition, Condition, IntoSystemConfigs, ScheduleLabel, SystemConfigs},
+    system::{
+        BoxedSystem, CommandQueue, EntityCommands, IntoSystem, PipeSystem, SystemId, SystemParam,
+    },
     world::World,
 };
-use bevy_hierarchy::{BuildWorldChildren, Parent};
-use bevy_log::trace;
+use bevy_hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt, Parent};
+use bevy_log::{error, trace, warn};
 use bevy_reflect::Reflect;
-use bevy_utils::intern::Interned;
+use bevy_utils::{intern::Interned, smallvec::SmallVec, HashSet};
 
 use bevy_event_entities_core::{
-    any_events, EventEntities, EventEntityReader, QueryEventReader, SendEventExt,
+    any_events, intercept_queued_events, send_event, EventEntities, EventEntityReader, SendEventExt,
 };
 
 pub use bevy_ecs::world::EntityRef;
 
+mod dynamic;
+mod index;
+mod storage;
+mod subscriptions;
+#[cfg(test)]
+mod testing;
+
+pub use dynamic::{AnyEvent, CallbackKey, DynamicEvent};
+pub use index::{update_callback_index, CallbackIndex};
+pub use storage::{cleanup_event_callbacks, CallbackStorage, EventCallbacks, StoredCallback};
+pub use subscriptions::{
+    cleanup_subscriptions, SubscribeExt, Subscribers, Subscription, Subscriptions,
+};
+
+use storage::{stored_callback, stored_callback_mut, stored_owner};
+#[cfg(test)]
+use testing::{test_world, TestEvent};
+
 pub mod prelude {
     pub use crate::{
-        AddCallbackExt, EventListenerPlugin, Listenable, Listener, SendEntityEventExt, Target,
+        AddCallbackExt, AddTraversalExt, AnyEvent, BroadcastEventExt, CallbackId,
+        CallbackPanicPolicy, CallbackStorage, DynamicEvent, EventContext, EventErrorHandler,
+        EventErrors, EventListenerPlugin, EventTraversal, Listenable, Listener, On, Phase,
+        PropagateSource, Propagation, RegisterCallbackExt, RemoveCallbackExt, SendEntityEventExt,
+        Source, SubscribeExt, Target, Targets, Traversal,
     };
 }
 
@@ -54,22 +80,33 @@ pub struct EventListenerSystems;
 
 pub fn event_listener_system_configs() -> SystemConfigs {
     IntoSystemConfigs::into_configs(
-        (propagate_events, run_callbacks)
+        (
+            cleanup_event_callbacks,
+            cleanup_subscriptions,
+            update_callback_index,
+            run_callbacks.run_if(any_events),
+        )
             .chain()
-            .run_if(any_events)
             .in_set(EventListenerSystems),
     )
 }
 
 pub struct EventListenerPlugin {
     schedule: Interned<dyn ScheduleLabel>,
+    error_handler: EventErrorHandler,
+    panic_policy: CallbackPanicPolicy,
+    callback_storage: CallbackStorage,
 }
 
 impl Plugin for EventListenerPlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.add_systems(EventListenerSchedule, event_listener_system_configs());
+        app.init_resource::<ListenerInput>()
+            .insert_resource(self.error_handler)
+            .insert_resource(self.panic_policy)
+            .insert_resource(self.callback_storage)
+            .add_systems(EventListenerSchedule, event_listener_system_configs());
         app.add_systems(
-            self.schedule.clone(),
+            self.schedule,
             run_event_listener_schedule.in_set(EventListenerSystems),
         );
     }
@@ -79,6 +116,9 @@ impl Default for EventListenerPlugin {
     fn default() -> Self {
         Self {
             schedule: PreUpdate.intern(),
+            error_handler: EventErrorHandler::default(),
+            panic_policy: CallbackPanicPolicy::default(),
+            callback_storage: CallbackStorage::default(),
         }
     }
 }
@@ -87,480 +127,2246 @@ impl EventListenerPlugin {
     pub fn new(schedule: impl ScheduleLabel) -> Self {
         Self {
             schedule: schedule.intern(),
+            error_handler: EventErrorHandler::default(),
+            panic_policy: CallbackPanicPolicy::default(),
+            callback_storage: CallbackStorage::default(),
         }
     }
-}
-
-#[derive(ScheduleLabel, Debug, PartialEq, Eq, Hash, Clone)]
-pub struct EventListenerSchedule;
-
-pub fn run_event_listener_schedule(world: &mut World) {
-    world.run_schedule(EventListenerSchedule);
-}
-
-#[derive(Component, Clone, PartialEq)]
-pub struct PropagatedEvent(pub Entity);
 
-pub fn propagate_events(
-    mut commands: Commands,
-    mut events: QueryEventReader<(Entity, &Target)>,
-    query: Query<&Parent>,
-) {
-    for (event, &Target(mut target)) in events.read() {
-        while let Ok(parent) = query.get(target) {
-            target = parent.get();
-            trace!("propagating event {event:?} to target {target:?}");
-            commands.send_event((Target(target), PropagatedEvent(event)));
-        }
+    /// Set how errors returned by callbacks are handled.
+    pub fn with_error_handler(mut self, error_handler: EventErrorHandler) -> Self {
+        self.error_handler = error_handler;
+        self
     }
-}
-
-pub fn run_callbacks(
-    world: &mut World,
-    mut reader: Local<EventEntityReader>,
-    mut events: Local<EventEntities>,
-) {
-    // we can't use `resource_scope` here because then callbacks would not be able to send new events.
-    mem::swap(
-        world
-            .resource_mut::<EventEntities>()
-            .bypass_change_detection(),
-        &mut events,
-    );
-
-    let mut queue = CommandQueue::default();
-    for event in reader.read(&events) {
-        let Some(target) = world
-            .get_entity(event)
-            .map(|e| e.get::<Target>().map(|t| t.0))
-        else {
-            continue;
-        };
-
-        let event = match world.get::<PropagatedEvent>(event) {
-            Some(p) => EventType::Propagated {
-                event: p.0,
-                propagated: event,
-            },
-            None => EventType::Event(event),
-        };
-
-        if !world.entities().contains(event.id()) {
-            continue;
-        }
-
-        let mut query = world.query::<(Entity, &CallbackIdent, Option<&Parent>)>();
-        for (callback_entity, ident, parent) in query.iter(world) {
-            if target
-                .and_then(|t| parent.map(|p| p.get() == t))
-                .unwrap_or(true)
-                && ident.entity_contains(world.entity(event.id()))
-                && event.entities_contains(world.entities())
-            {
-                trace!("running callback {callback_entity:?} for event {event:?} with target {target:?}");
-                queue.push(move |world: &mut World| {
-                    if !event.entities_contains(world.entities()) {
-                        trace!("event {:?} no longer exists", event.id());
-                        return;
-                    }
-
-                    // set the input for the callback
-                    world.insert_resource(ListenerInput { event_type: event });
-
-                    // take the callback from the entity temporarily to run it
-                    let Some(mut callback) = world
-                        .get_entity_mut(callback_entity)
-                        .and_then(|mut c| c.take::<CallbackSystemInner>())
-                    else {
-                        return;
-                    };
-
-                    // replace the target of the propagated event with the target of the actual event.
-                    event.swap_target(world);
-
-                    // run the callback
-                    let name = callback.name();
-                    panic::catch_unwind(AssertUnwindSafe(|| callback.run(world))).unwrap_or_else(
-                        |_| {
-                            panic!(
-                                "Encountered a panic in callback system `{name}`!
-                callback: {callback_entity:?}, event: {event:?}, target: {target:?}"
-                            );
-                        },
-                    );
-
-                    // restore the target to the previous value
-                    event.swap_target(world);
 
-                    // put the callback back into the entity if it still exists
-                    if let Some(mut e) = world.get_entity_mut(callback_entity) {
-                        e.insert(callback);
-                    }
-                });
-            }
-        }
-        queue.apply(world);
-        world.remove_resource::<ListenerInput>();
+    /// Set what happens when a callback panics.
+    pub fn with_panic_policy(mut self, panic_policy: CallbackPanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
     }
 
-    // add any new events
-    let mut new_events = world.resource_mut::<EventEntities>();
-    events.extend(new_events.drain());
-    mem::swap(new_events.bypass_change_detection(), &mut events);
+    /// Set where the callbacks added to entities are stored.
+    pub fn with_callback_storage(mut self, callback_storage: CallbackStorage) -> Self {
+        self.callback_storage = callback_storage;
+        self
+    }
 }
 
-pub trait SendEntityEventExt {
-    fn send_event(&mut self, event: impl Bundle) -> &mut Self;
+/// How errors returned by callbacks are handled, see [`EventListenerPlugin::with_error_handler`].
+#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventErrorHandler {
+    /// Log the error.
+    #[default]
+    Log,
+    /// Panic with the error.
+    Panic,
+    /// Collect the error into the [`EventErrors`] resource.
+    Collect,
 }
 
-impl<'a> SendEntityEventExt for EntityCommands<'a> {
-    fn send_event(&mut self, event: impl Bundle) -> &mut Self {
-        let target = self.id();
-        self.commands().send_event((Target(target), event));
-        self
+impl EventErrorHandler {
+    fn handle(self, world: &mut World, error: EventError) {
+        match self {
+            EventErrorHandler::Log => error!("{error}"),
+            EventErrorHandler::Panic => panic!("{error}"),
+            EventErrorHandler::Collect => world
+                .get_resource_or_insert_with(EventErrors::default)
+                .0
+                .push(error),
+        }
     }
 }
 
-#[derive(Component, Reflect, Debug, PartialEq, Clone, Copy)]
-/// Add this to an event to make it listenable.
-pub struct Target(pub Entity);
+/// An error returned by a callback.
+#[derive(Debug)]
+pub struct EventError {
+    /// The id of the callback.
+    pub callback: CallbackId,
+    /// The name of the callback system.
+    pub name: Cow<'static, str>,
+    pub context: EventContext,
+    pub error: BoxedError,
+}
 
-impl Listenable for Target {
-    fn entity_contains(entity: EntityRef) -> bool {
-        entity.contains::<Target>()
+impl std::fmt::Display for EventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "callback system `{}` returned an error: {} (callback: {:?}, event: {:?}, target: {:?})",
+            self.name, self.error, self.callback, self.context.event, self.context.current_target
+        )
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum EventType {
-    Propagated { propagated: Entity, event: Entity },
-    Event(Entity),
+/// What happens when a callback panics, see [`EventListenerPlugin::with_panic_policy`].
+///
+/// Except for [`CallbackPanicPolicy::Propagate`], the panics are recorded in the [`CallbackPanics`] resource.
+#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CallbackPanicPolicy {
+    /// Panic again with the context of the callback.
+    #[default]
+    Propagate,
+    /// Log the panic and keep running the callback for future events.
+    LogAndContinue,
+    /// Log the panic and remove the [`CallbackSystemInner`] from the callback, so it never runs again.
+    DisableCallback,
 }
 
-impl EventType {
-    #[inline]
-    pub fn is_propagated(&self) -> bool {
-        matches!(self, EventType::Propagated { .. })
-    }
-
-    #[inline]
-    pub fn id(&self) -> Entity {
-        match self {
-            EventType::Propagated { event, .. } => *event,
-            EventType::Event(event) => *event,
+impl CallbackPanicPolicy {
+    /// Returns `true` if the callback should be kept.
+    fn handle(self, world: &mut World, panic: CallbackPanic) -> bool {
+        if self == CallbackPanicPolicy::Propagate {
+            panic!("{panic}");
         }
+        error!("{panic}");
+        world
+            .get_resource_or_insert_with(CallbackPanics::default)
+            .0
+            .push(panic);
+        self == CallbackPanicPolicy::LogAndContinue
     }
+}
 
-    fn entities_contains(&self, entities: &Entities) -> bool {
-        match self {
-            EventType::Propagated {
-                propagated: entity,
-                event,
-            } => entities.contains(*entity) && entities.contains(*event),
-            EventType::Event(event) => entities.contains(*event),
-        }
-    }
+/// A panic in a callback.
+#[derive(Debug, Clone)]
+pub struct CallbackPanic {
+    /// The id of the callback.
+    pub callback: CallbackId,
+    /// The name of the callback system.
+    pub name: Cow<'static, str>,
+    pub context: EventContext,
+    /// The panic message, if it was a string.
+    pub message: Option<String>,
+}
 
-    fn swap_target(&self, world: &mut World) -> bool {
-        match self {
-            EventType::Propagated {
-                propagated: entity,
-                event,
-            } => {
-                if !self.entities_contains(world.entities()) {
-                    return false;
-                }
-                assert_ne!(*entity, *event);
-                let cell = world.as_unsafe_world_cell();
-
-                // Safety: the entities are not the same
-                let swap = || unsafe {
-                    let mut src = cell.get_entity(*entity)?.get_mut::<Target>()?;
-                    let mut dst = cell.get_entity(*event)?.get_mut::<Target>()?;
-                    mem::swap(&mut src.0, &mut dst.0);
-                    Some(())
-                };
-                swap().is_some()
-            }
-            EventType::Event(_) => false,
-        }
+impl CallbackPanic {
+    fn message(payload: &(dyn std::any::Any + Send)) -> Option<String> {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
     }
 }
 
-#[derive(Resource, Debug, PartialEq, Clone)]
-pub struct ListenerInput {
-    pub event_type: EventType,
+impl std::fmt::Display for CallbackPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Encountered a panic in callback system `{}`: {} (callback: {:?}, event: {:?}, target: {:?})",
+            self.name,
+            self.message.as_deref().unwrap_or("Box<dyn Any>"),
+            self.callback,
+            self.context.event,
+            self.context.current_target
+        )
+    }
 }
 
-#[derive(SystemParam, Debug)]
-pub struct Listener<'w, 's, D = (), F = ()>
-where
-    D: QueryData + 'static,
-    F: QueryFilter + 'static,
-{
-    input: Res<'w, ListenerInput>,
-    query: Query<'w, 's, D, F>,
-}
+/// The panics in callbacks, recorded unless the [`CallbackPanicPolicy`] is [`CallbackPanicPolicy::Propagate`].
+#[derive(Resource, Default, Debug)]
+pub struct CallbackPanics(Vec<CallbackPanic>);
 
-impl<'w, 's, D: QueryData, F: QueryFilter> Listener<'w, 's, D, F> {
-    #[inline]
-    pub fn event_type(&self) -> EventType {
-        self.input.event_type
+impl CallbackPanics {
+    pub fn iter(&self) -> impl Iterator<Item = &CallbackPanic> {
+        self.0.iter()
     }
 
-    /// Returns true if the event is propagated. Ie. it is not the root event.
-    #[inline]
-    pub fn is_propagated(&self) -> bool {
-        self.event_type().is_propagated()
+    /// Remove all panics, returning them in the order they occurred.
+    pub fn drain(&mut self) -> impl Iterator<Item = CallbackPanic> + '_ {
+        self.0.drain(..)
     }
 
-    /// Retrieve an immutable reference to the event data from the query.
-    ///
-    /// # Panics
-    ///
-    /// Will panic if the entity is not in the query.
     #[inline]
-    #[track_caller]
-    pub fn event(&self) -> ROQueryItem<'_, D> {
-        self.get_event().unwrap()
+    pub fn len(&self) -> usize {
+        self.0.len()
     }
 
-    /// Retrieve a mutable reference to the event data from the query.
-    ///
-    /// # Panics
-    ///
-    /// Will panic if the entity is not in the query.
     #[inline]
-    #[track_caller]
-    pub fn event_mut(&mut self) -> QueryItem<'_, D> {
-        self.get_event_mut().unwrap()
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
+}
 
-    /// Retrieve an immutable reference to the event data from the query.
-    #[inline]
-    pub fn get_event(&self) -> Result<ROQueryItem<'_, D>, bevy_ecs::query::QueryEntityError> {
-        self.query.get(self.input.event_type.id())
-    }
+/// The errors returned by callbacks, when using [`EventErrorHandler::Collect`].
+#[derive(Resource, Default, Debug)]
+pub struct EventErrors(Vec<EventError>);
 
-    /// Retrieve a mutable reference to the event data from the query.
-    #[inline]
-    pub fn get_event_mut(&mut self) -> Result<QueryItem<'_, D>, bevy_ecs::query::QueryEntityError> {
-        self.query.get_mut(self.input.event_type.id())
+impl EventErrors {
+    pub fn iter(&self) -> impl Iterator<Item = &EventError> {
+        self.0.iter()
     }
 
-    /// Returns the entity of the event or the Propagated(entity) if the event is propagated
-    #[inline]
-    pub fn id(&self) -> Entity {
-        self.input.event_type.id()
+    /// Remove all errors, returning them in the order they occurred.
+    pub fn drain(&mut self) -> impl Iterator<Item = EventError> + '_ {
+        self.0.drain(..)
     }
 
     #[inline]
-    pub fn query(&self) -> &Query<'w, 's, D, F> {
-        &self.query
+    pub fn len(&self) -> usize {
+        self.0.len()
     }
 
     #[inline]
-    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
-        &mut self.query
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
 }
 
-#[derive(Component)]
-pub struct CallbackIdent {
-    fn_entity_contains: fn(EntityRef) -> bool,
+#[derive(ScheduleLabel, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EventListenerSchedule;
+
+pub fn run_event_listener_schedule(world: &mut World) {
+    world.run_schedule(EventListenerSchedule);
 }
 
-impl CallbackIdent {
-    pub fn new<T: Listenable>() -> Self {
-        Self {
-            fn_entity_contains: |entity| T::entity_contains(entity),
+pub fn run_callbacks(world: &mut World, mut reader: Local<EventEntityReader>) {
+    // events sent with `send_event` since the last run may still be queued
+    intercept_queued_events(world);
+    // collect the events up front, so that callbacks are able to send new events.
+    let events: Vec<Entity> = reader.read(world.resource::<EventEntities>()).collect();
+
+    let mut dispatch = Dispatch::default();
+    for event in events {
+        let Some(entity) = world.get_entity(event) else {
+            continue;
+        };
+        let target = entity.get::<Target>().map(|t| t.0);
+        let multiple = entity.get::<Targets>();
+        let source = entity.get::<Source>().map(|s| s.0);
+        let propagate_source = entity.contains::<PropagateSource>();
+        dispatch.targets.clear();
+        dispatch.targets.extend(target);
+        if let Some(Targets(targets)) = multiple {
+            dispatch.targets.extend(targets.iter().copied());
         }
-    }
 
-    pub fn entity_contains(&self, entity: EntityRef) -> bool {
-        (self.fn_entity_contains)(entity)
+        if multiple.is_some() {
+            // dispatch the same event to each target in turn, the global callbacks only run for the first one
+            for i in 0..dispatch.targets.len() {
+                let target = dispatch.targets[i];
+                dispatch.run(world, event, Some(target), None, i == 0);
+            }
+        } else {
+            dispatch.run(world, event, target, None, true);
+        }
+        // after the target, so the source can react to what happened to the target
+        if let Some(source) = source {
+            dispatch.run(world, event, target, Some((source, propagate_source)), true);
+        }
     }
 }
 
-#[derive(Component)]
-pub enum CallbackSystemInner {
-    Pending(Option<BoxedSystem>),
-    Ready(BoxedSystem),
+/// The buffers used to dispatch the events, reused between events.
+#[derive(Default)]
+struct Dispatch {
+    queue: CommandQueue,
+    callbacks: Vec<CallbackId>,
+    path: Vec<Entity>,
+    targets: SmallVec<[Entity; 4]>,
+    steps: Vec<(Phase, usize)>,
 }
 
-pub struct CallbackSystem {
-    inner: CallbackSystemInner,
-    name: Cow<'static, str>,
-}
+impl Dispatch {
+    /// Dispatch the event to the callbacks of the target and its ancestors.
+    ///
+    /// With a `source`, dispatch it to the [`Phase::Source`] callbacks of the [`Source`] instead,
+    /// and to its ancestors if the `bool` is `true`.
+    ///
+    /// The global callbacks only run if `global` is `true`.
+    fn run(
+        &mut self,
+        world: &mut World,
+        event: Entity,
+        original_target: Option<Entity>,
+        source: Option<(Entity, bool)>,
+        global: bool,
+    ) {
+        // the callbacks of the previous targets or of the target may have consumed the event
+        if world.get_entity(event).is_none() {
+            return;
+        }
+        let start = source.map_or(original_target, |(source, _)| Some(source));
+        // collect the path up front, since callbacks may despawn the entities along the way
+        let path = &mut self.path;
+        path.clear();
+        if let Some(mut entity) = start {
+            path.push(entity);
+            let traversal = match source {
+                Some((_, false)) => EventTraversal::none(),
+                _ => EventTraversal::of(world.entity(event), world),
+            };
+            while let Some(next) = world.get_entity(entity).and_then(|e| traversal.next(e)) {
+                if path.contains(&next) {
+                    warn!("event {event:?} is propagating in a cycle, stopping at {entity:?}");
+                    break;
+                }
+                entity = next;
+                path.push(entity);
+            }
+        }
 
-impl CallbackSystem {
-    pub fn new<M>(system: impl IntoSystem<(), (), M>) -> Self {
-        let system: Box<dyn System<In = (), Out = ()>> = Box::new(IntoSystem::into_system(system));
-        let name = system.name();
-        Self {
-            inner: CallbackSystemInner::Pending(Some(system)),
-            name,
+        let len = path.len();
+        let mut input = world.get_resource_or_insert_with(ListenerInput::default);
+        *input = ListenerInput::new(EventType::Event(event), Phase::Target);
+        input.original_target = original_target;
+        input.current_target = start;
+        input.path = mem::take(path);
+        input.targets.clone_from(&self.targets);
+
+        self.steps.clear();
+        if source.is_some() {
+            // from the source up to the root
+            self.steps
+                .extend((0..len).map(|depth| (Phase::Source, depth)));
+        } else {
+            // capture from the root down to the target, then bubble back up to the root
+            self.steps
+                .extend((1..len).rev().map(|depth| (Phase::Capture, depth)));
+            self.steps.push((Phase::Target, 0));
+            self.steps
+                .extend((1..len).map(|depth| (Phase::Bubble, depth)));
         }
-    }
+        for &(phase, depth) in &self.steps {
+            // stop propagating if the event was despawned
+            if !world.entities().contains(event) {
+                break;
+            }
 
-    pub fn name(&self) -> &str {
-        &self.name
-    }
+            let current = match depth {
+                0 => start,
+                _ => world
+                    .get_resource::<ListenerInput>()
+                    .and_then(|input| input.path.get(depth).copied()),
+            };
+            let event = match current {
+                Some(target) if depth > 0 => EventType::Propagated { event, target },
+                _ => EventType::Event(event),
+            };
+            if event.is_propagated() {
+                trace!(
+                    "propagating event {:?} to target {current:?} ({phase:?})",
+                    event.id()
+                );
+            }
+
+            if let Some(index) = world.get_resource::<CallbackIndex>() {
+                index.matching(
+                    current,
+                    world.entity(event.id()),
+                    phase,
+                    global,
+                    &mut self.callbacks,
+                );
+            }
+            for callback_id in self.callbacks.drain(..) {
+                trace!(
+                    "running callback {callback_id:?} for event {event:?} with target {current:?}"
+                );
+                self.queue.push(move |world: &mut World| {
+                    run_callback(
+                        world,
+                        callback_id,
+                        event,
+                        phase,
+                        original_target,
+                        current,
+                        depth,
+                    );
+                });
+            }
+            self.queue.apply(world);
 
-    pub fn run(&mut self, world: &mut World) {
-        self.inner.run(world);
+            if world
+                .get_resource::<ListenerInput>()
+                .is_some_and(|input| input.is_propagation_stopped())
+            {
+                trace!("propagation of event {:?} was stopped", event.id());
+                break;
+            }
+        }
+        // reset the input, reusing the allocation of the path for the next event
+        if let Some(mut input) = world.get_resource_mut::<ListenerInput>() {
+            self.path = mem::take(&mut *input).path;
+            self.path.clear();
+        }
     }
 }
 
-impl CallbackSystemInner {
-    pub fn new<M>(system: impl IntoSystem<(), (), M>) -> Self {
-        Self::Pending(Some(Box::new(IntoSystem::into_system(system))))
+/// Run a single callback for an event, if it still exists and its conditions are met.
+fn run_callback(
+    world: &mut World,
+    callback_id: CallbackId,
+    event: EventType,
+    phase: Phase,
+    original_target: Option<Entity>,
+    current: Option<Entity>,
+    depth: usize,
+) {
+    if !world.entities().contains(event.id()) {
+        trace!("event {:?} no longer exists", event.id());
+        return;
     }
 
-    pub fn name(&self) -> Cow<'static, str> {
-        match self {
-            CallbackSystemInner::Pending(system) => system.as_ref().unwrap().name(),
-            CallbackSystemInner::Ready(system) => system.name(),
+    // set the input for the callback
+    match world.get_resource_mut::<ListenerInput>() {
+        Some(input) if input.is_immediate_propagation_stopped() => {
+            trace!(
+                "immediate propagation of event {:?} was stopped",
+                event.id()
+            );
+            return;
+        }
+        Some(mut input) => {
+            input.event_type = event;
+            input.phase = phase;
+            input.current_target = current;
+            input.depth = depth;
+        }
+        None => {
+            let mut input = ListenerInput::new(event, phase);
+            input.original_target = original_target;
+            input.current_target = current;
+            input.depth = depth;
+            world.insert_resource(input);
         }
     }
+    let context = world.resource::<ListenerInput>().context();
 
-    pub fn run(&mut self, world: &mut World) {
-        match self {
-            CallbackSystemInner::Pending(system) => {
-                let mut system = system.take().unwrap();
-                system.initialize(world);
-                system.run((), world);
-                system.apply_deferred(world);
-                *self = CallbackSystemInner::Ready(system);
-                // let id = world.register_boxed_system(system.take().unwrap());
-                // world.run_system(id).unwrap();
-                // *self = CallbackSystemInner::Ready(id);
+    if remaining_runs(world, callback_id).is_some_and(|runs| runs.0 == 0) {
+        despawn_callback(world, callback_id);
+        return;
+    }
+
+    // skip disabled callbacks and callbacks whose conditions aren't met
+    if !is_enabled(world, callback_id) || !CallbackConditions::run(world, callback_id) {
+        trace!("skipping callback {callback_id:?}");
+        return;
+    }
+
+    // take the callback from the entity temporarily to run it
+    let Some(mut callback) = take_callback_system(world, callback_id) else {
+        return;
+    };
+
+    // run the callback
+    let name = callback.name();
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| callback.run(context, world))) {
+        Ok(result) => result,
+        Err(payload) => {
+            // a panicking shared system is removed from the world, so the callbacks using it are useless
+            let shared = match &callback {
+                CallbackSystemInner::Shared(shared) => Some(shared.id),
+                _ => None,
+            };
+            if let Some(id) = shared {
+                despawn_shared_callbacks(world, id);
+                despawn_callback(world, callback_id);
             }
-            CallbackSystemInner::Ready(system) => {
-                system.run((), world);
-                system.apply_deferred(world);
-                // world.run_system(*id).unwrap();
+            let panic = CallbackPanic {
+                callback: callback_id,
+                name: name.clone(),
+                context,
+                message: CallbackPanic::message(payload.as_ref()),
+            };
+            let policy = world
+                .get_resource::<CallbackPanicPolicy>()
+                .copied()
+                .unwrap_or_default();
+            if !policy.handle(world, panic) || shared.is_some() {
+                // drop the callback system, leaving the callback disabled
+                return;
             }
+            Ok(Propagation::Continue)
         }
-    }
-}
-
-#[derive(Component)]
-pub struct On<T: Listenable> {
-    ident: CallbackIdent,
-    system: CallbackSystemInner,
-    marker: PhantomData<T>,
-}
+    };
 
-impl<T: Listenable> On<T> {
-    pub fn run<M>(system: impl IntoSystem<(), (), M>) -> Self {
-        Self {
-            marker: PhantomData,
-            ident: CallbackIdent::new::<T>(),
-            system: CallbackSystemInner::new(system),
+    // remove the callback if it ran out of runs, otherwise put it back into the entity if it still exists
+    let exhausted = remaining_runs(world, callback_id).is_some_and(|runs| {
+        runs.0 = runs.0.saturating_sub(1);
+        runs.0 == 0
+    });
+    if exhausted {
+        despawn_callback(world, callback_id);
+    } else {
+        match callback_id {
+            CallbackId::Entity(entity) => {
+                if let Some(mut entity) = world.get_entity_mut(entity) {
+                    entity.insert(callback);
+                }
+            }
+            CallbackId::Stored(_) => {
+                if let Some(stored) = stored_callback_mut(world, callback_id) {
+                    stored.system = Some(callback);
+                }
+            }
         }
     }
 
-    fn into_bundle(self) -> (CallbackIdent, CallbackSystemInner) {
-        (self.ident, self.system)
+    match result {
+        Ok(Propagation::Continue) => {}
+        Ok(Propagation::Stop) => {
+            if let Some(input) = world.get_resource::<ListenerInput>() {
+                input.stop_propagation();
+            }
+        }
+        Err(error) => {
+            let error = EventError {
+                callback: callback_id,
+                name,
+                context,
+                error,
+            };
+            world
+                .get_resource::<EventErrorHandler>()
+                .copied()
+                .unwrap_or_default()
+                .handle(world, error);
+        }
     }
 }
 
-pub trait IntoCallback<T, M>: Send + Sync + 'static {
-    fn into_bundle(self) -> (CallbackIdent, CallbackSystemInner);
-}
-
-impl<T: Listenable> IntoCallback<T, ()> for On<T> {
-    #[inline]
-    fn into_bundle(self) -> (CallbackIdent, CallbackSystemInner) {
-        self.into_bundle()
+/// Returns the [`RemainingRuns`] of the callback entity or the stored callback.
+fn remaining_runs(world: &mut World, callback: CallbackId) -> Option<&mut RemainingRuns> {
+    match callback {
+        CallbackId::Entity(entity) => world.get_mut::<RemainingRuns>(entity).map(Mut::into_inner),
+        CallbackId::Stored(_) => stored_callback_mut(world, callback)?.runs.as_mut(),
     }
 }
 
-impl<T: Listenable> IntoCallback<T, ()> for CallbackSystemInner {
-    #[inline]
-    fn into_bundle(self) -> (CallbackIdent, CallbackSystemInner) {
-        (CallbackIdent::new::<T>(), self)
+/// Returns `false` if the callback entity or the stored callback is disabled.
+fn is_enabled(world: &World, callback: CallbackId) -> bool {
+    match callback {
+        CallbackId::Entity(entity) => world.get::<Enabled>(entity).map_or(true, |e| e.0),
+        CallbackId::Stored(_) => stored_callback(world, callback).map_or(true, |s| s.enabled),
     }
 }
 
-impl<M, T: Listenable, S: IntoSystem<(), (), M> + Send + Sync + 'static> IntoCallback<T, M> for S {
-    #[inline]
-    fn into_bundle(self) -> (CallbackIdent, CallbackSystemInner) {
-        (CallbackIdent::new::<T>(), CallbackSystemInner::new(self))
+/// Take the system out of the callback entity or the stored callback.
+fn take_callback_system(world: &mut World, callback: CallbackId) -> Option<CallbackSystemInner> {
+    match callback {
+        CallbackId::Entity(entity) => world.get_entity_mut(entity)?.take::<CallbackSystemInner>(),
+        CallbackId::Stored(_) => stored_callback_mut(world, callback)?.system.take(),
     }
 }
 
-pub trait AddCallbackExt {
-    /// Run a system when the event matching `T` is triggered.
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self;
+pub trait SendEntityEventExt {
+    fn send_event(&mut self, event: impl Bundle) -> &mut Self;
+
+    /// Send an event with this entity as its [`Source`].
+    fn send_event_from(&mut self, event: impl Bundle) -> &mut Self;
 }
 
-impl AddCallbackExt for World {
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self {
-        self.spawn(callback.into_bundle());
+impl<'a> SendEntityEventExt for EntityCommands<'a> {
+    fn send_event(&mut self, event: impl Bundle) -> &mut Self {
+        let target = self.id();
+        self.commands().send_event((Target(target), event));
         self
     }
-}
 
-impl AddCallbackExt for App {
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self {
-        self.world.add_callback(callback);
+    fn send_event_from(&mut self, event: impl Bundle) -> &mut Self {
+        let source = self.id();
+        self.commands().send_event((Source(source), event));
         self
     }
 }
 
-impl<'w, 's> AddCallbackExt for Commands<'w, 's> {
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self {
-        self.add(move |world: &mut World| {
-            world.add_callback(callback);
-        });
+pub trait BroadcastEventExt {
+    /// Send one event to every entity matching the filter `F`, except callbacks and events.
+    ///
+    /// The event isn't propagated, each matching entity is a target of it, see [`Targets`].
+    fn broadcast_event<F: QueryFilter + 'static>(&mut self, event: impl Bundle) -> &mut Self;
+
+    /// Send one event to `root` and all of its descendants, from the root down.
+    /// Callbacks and events in the hierarchy are skipped, along with their descendants.
+    ///
+    /// The event isn't propagated, each entity in the hierarchy is a target of it, see [`Targets`].
+    fn tunnel_event(&mut self, root: Entity, event: impl Bundle) -> &mut Self;
+}
+
+impl BroadcastEventExt for World {
+    fn broadcast_event<F: QueryFilter + 'static>(&mut self, event: impl Bundle) -> &mut Self {
+        let events = current_events(self);
+        let targets: Targets = self
+            .query_filtered::<Entity, (F, Without<CallbackIdent>)>()
+            .iter(self)
+            .filter(|entity| !events.contains(entity))
+            .collect();
+        send_event(self, (event, targets, EventTraversal::none()));
         self
     }
-}
 
-impl<'w> AddCallbackExt for EntityWorldMut<'w> {
+    fn tunnel_event(&mut self, root: Entity, event: impl Bundle) -> &mut Self {
+        let events = current_events(self);
+        let mut targets = Targets::default();
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            if events.contains(&entity) || self.get::<CallbackIdent>(entity).is_some() {
+                continue;
+            }
+            targets.0.push(entity);
+            if let Some(children) = self.get::<Children>(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        send_event(self, (event, targets, EventTraversal::none()));
+        self
+    }
+}
+
+/// The events in [`EventEntities`], which broadcast and tunneled events don't target.
+fn current_events(world: &World) -> HashSet<Entity> {
+    world
+        .get_resource::<EventEntities>()
+        .map(|events| events.iter().collect())
+        .unwrap_or_default()
+}
+
+impl<'w, 's> BroadcastEventExt for Commands<'w, 's> {
+    fn broadcast_event<F: QueryFilter + 'static>(&mut self, event: impl Bundle) -> &mut Self {
+        self.add(|world: &mut World| {
+            world.broadcast_event::<F>(event);
+        });
+        self
+    }
+
+    fn tunnel_event(&mut self, root: Entity, event: impl Bundle) -> &mut Self {
+        self.add(move |world: &mut World| {
+            world.tunnel_event(root, event);
+        });
+        self
+    }
+}
+
+#[derive(Component, Reflect, Debug, PartialEq, Clone, Copy)]
+/// Add this to an event to make it listenable.
+pub struct Target(pub Entity);
+
+/// Matches events with a [`Target`] or [`Targets`].
+impl Listenable for Target {
+    fn entity_contains(entity: EntityRef) -> bool {
+        entity.contains::<Target>() || entity.contains::<Targets>()
+    }
+}
+
+/// Add this to an event to dispatch it to multiple targets.
+///
+/// The event is dispatched to each target in turn, after the [`Target`] if it also has one.
+/// Global callbacks only run while the event is dispatched to the first target,
+/// [`Listener::targets`] returns all of them.
+#[derive(Component, Default, Debug, PartialEq, Clone)]
+pub struct Targets(pub SmallVec<[Entity; 4]>);
+
+impl FromIterator<Entity> for Targets {
+    fn from_iter<I: IntoIterator<Item = Entity>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Listenable for Targets {
+    fn entity_contains(entity: EntityRef) -> bool {
+        entity.contains::<Targets>()
+    }
+}
+
+/// Add this to an event to dispatch it to the entity that caused it, in the [`Phase::Source`] phase.
+///
+/// Source callbacks are added with [`On::from_source`].
+#[derive(Component, Reflect, Debug, PartialEq, Clone, Copy)]
+pub struct Source(pub Entity);
+
+impl Listenable for Source {
+    fn entity_contains(entity: EntityRef) -> bool {
+        entity.contains::<Source>()
+    }
+}
+
+/// Add this to an event to also dispatch it to the ancestors of its [`Source`].
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct PropagateSource;
+
+/// A relation that events propagate along, like [`Parent`].
+///
+/// ```ignore
+/// #[derive(Component)]
+/// struct Owner(Entity);
+///
+/// impl Traversal for Owner {
+///     fn traverse(&self) -> Option<Entity> {
+///         Some(self.0)
+///     }
+/// }
+///
+/// app.add_traversal::<Attack, Owner>();
+/// ```
+pub trait Traversal: Component {
+    /// Returns the next entity to propagate the event to, or `None` to stop propagating.
+    fn traverse(&self) -> Option<Entity>;
+}
+
+impl Traversal for Parent {
+    fn traverse(&self) -> Option<Entity> {
+        Some(self.get())
+    }
+}
+
+/// Decides which [`Traversal`] an event propagates along.
+///
+/// Insert this on an event to override the traversal for that event.
+/// Otherwise the traversal registered for the event with [`AddTraversalExt::add_traversal`] is used,
+/// falling back to [`Parent`].
+#[derive(Component, Clone, Copy)]
+pub struct EventTraversal {
+    fn_traverse: Option<fn(EntityRef) -> Option<Entity>>,
+}
+
+impl EventTraversal {
+    /// Propagate the event along `R`.
+    pub fn new<R: Traversal>() -> Self {
+        Self {
+            fn_traverse: Some(|entity| entity.get::<R>().and_then(R::traverse)),
+        }
+    }
+
+    /// Don't propagate the event.
+    pub fn none() -> Self {
+        Self { fn_traverse: None }
+    }
+
+    /// Returns the next entity to propagate the event to.
+    pub fn next(&self, entity: EntityRef) -> Option<Entity> {
+        self.fn_traverse.and_then(|traverse| traverse(entity))
+    }
+
+    /// Returns the traversal of an event.
+    pub fn of(event: EntityRef, world: &World) -> Self {
+        if let Some(traversal) = event.get::<EventTraversal>() {
+            return *traversal;
+        }
+        world
+            .get_resource::<Traversals>()
+            .and_then(|traversals| {
+                traversals
+                    .0
+                    .iter()
+                    .find(|(ident, _)| ident.entity_contains(event))
+            })
+            .map_or_else(Self::new::<Parent>, |(_, traversal)| *traversal)
+    }
+}
+
+/// The traversals registered for [`Listenable`] types, see [`AddTraversalExt::add_traversal`].
+#[derive(Resource, Default)]
+pub struct Traversals(Vec<(CallbackIdent, EventTraversal)>);
+
+pub trait AddTraversalExt {
+    /// Propagate the events matching `T` along `R` instead of [`Parent`].
+    fn add_traversal<T: Listenable, R: Traversal>(&mut self) -> &mut Self;
+}
+
+impl AddTraversalExt for World {
+    fn add_traversal<T: Listenable, R: Traversal>(&mut self) -> &mut Self {
+        let mut traversals = self.get_resource_or_insert_with(Traversals::default);
+        let ident = CallbackIdent::new::<T>();
+        traversals.0.retain(|(i, _)| i.key != ident.key);
+        traversals.0.push((ident, EventTraversal::new::<R>()));
+        self
+    }
+}
+
+impl AddTraversalExt for App {
+    fn add_traversal<T: Listenable, R: Traversal>(&mut self) -> &mut Self {
+        self.world.add_traversal::<T, R>();
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EventType {
+    /// The event is propagated to `target`, an ancestor of the original [`Target`].
+    Propagated {
+        event: Entity,
+        target: Entity,
+    },
+    Event(Entity),
+}
+
+impl EventType {
+    #[inline]
+    pub fn is_propagated(&self) -> bool {
+        matches!(self, EventType::Propagated { .. })
+    }
+
+    #[inline]
+    pub fn id(&self) -> Entity {
+        match self {
+            EventType::Propagated { event, .. } => *event,
+            EventType::Event(event) => *event,
+        }
+    }
+}
+
+/// The phase of the event dispatch.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Phase {
+    /// The event is going from the root down to the [`Target`]. Only capture callbacks run in this phase.
+    Capture,
+    /// The event has reached the [`Target`]. Both capture and bubble callbacks run in this phase.
+    Target,
+    /// The event is going from the [`Target`] up to the root. Only bubble callbacks run in this phase.
+    Bubble,
+    /// The event is dispatched to its [`Source`], after the other phases. Only source callbacks run in this phase.
+    Source,
+}
+
+#[derive(Resource, Debug)]
+pub struct ListenerInput {
+    pub event_type: EventType,
+    pub phase: Phase,
+    /// The [`Target`] of the event.
+    pub original_target: Option<Entity>,
+    /// The entity whose callbacks are currently running.
+    pub current_target: Option<Entity>,
+    /// The index of [`ListenerInput::current_target`] in [`ListenerInput::path`].
+    pub depth: usize,
+    /// The entities the event propagates through, starting at the [`Target`], or at the [`Source`] in the [`Phase::Source`] phase.
+    pub path: Vec<Entity>,
+    /// The [`Target`] of the event, followed by its [`Targets`].
+    pub targets: SmallVec<[Entity; 4]>,
+    // atomics, so that `Listener` stays a read-only system param
+    propagation_stopped: AtomicBool,
+    immediate_propagation_stopped: AtomicBool,
+}
+
+impl Default for ListenerInput {
+    fn default() -> Self {
+        Self::new(EventType::Event(Entity::PLACEHOLDER), Phase::Target)
+    }
+}
+
+impl ListenerInput {
+    pub fn new(event_type: EventType, phase: Phase) -> Self {
+        Self {
+            event_type,
+            phase,
+            original_target: None,
+            current_target: None,
+            depth: 0,
+            path: Vec::new(),
+            targets: SmallVec::new(),
+            propagation_stopped: AtomicBool::new(false),
+            immediate_propagation_stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the input of the callback system.
+    pub fn context(&self) -> EventContext {
+        EventContext {
+            event: self.event_type.id(),
+            phase: self.phase,
+            original_target: self.original_target,
+            current_target: self.current_target,
+            depth: self.depth,
+        }
+    }
+
+    /// Stop the event from propagating to the next ancestor.
+    pub fn stop_propagation(&self) {
+        self.propagation_stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop the event from propagating, and skip the remaining callbacks on the current entity.
+    pub fn stop_immediate_propagation(&self) {
+        self.stop_propagation();
+        self.immediate_propagation_stopped
+            .store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped.load(Ordering::Relaxed)
+    }
+
+    pub fn is_immediate_propagation_stopped(&self) -> bool {
+        self.immediate_propagation_stopped.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(SystemParam, Debug)]
+pub struct Listener<'w, 's, D = (), F = ()>
+where
+    D: QueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    input: Res<'w, ListenerInput>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> Listener<'w, 's, D, F> {
+    #[inline]
+    pub fn event_type(&self) -> EventType {
+        self.input.event_type
+    }
+
+    /// Returns the same [`EventContext`] that callbacks taking `In<EventContext>` receive.
+    #[inline]
+    pub fn context(&self) -> EventContext {
+        self.input.context()
+    }
+
+    /// Returns true if the event is propagated. Ie. it is not the root event.
+    #[inline]
+    pub fn is_propagated(&self) -> bool {
+        self.event_type().is_propagated()
+    }
+
+    /// Returns the phase of the event dispatch.
+    #[inline]
+    pub fn phase(&self) -> Phase {
+        self.input.phase
+    }
+
+    /// Returns the [`Target`] the event was sent to.
+    #[inline]
+    pub fn original_target(&self) -> Option<Entity> {
+        self.input.original_target
+    }
+
+    /// Returns the entity whose callbacks are currently running.
+    ///
+    /// This is the [`Target`] of the event, or one of its ancestors if the event is propagated.
+    #[inline]
+    pub fn current_target(&self) -> Option<Entity> {
+        self.input.current_target
+    }
+
+    /// Returns how many times the event has propagated to reach [`Listener::current_target`].
+    ///
+    /// The depth at the [`Target`] is 0.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.input.depth
+    }
+
+    /// Returns the entities the event propagates through, starting at the [`Target`], or at the [`Source`] in the [`Phase::Source`] phase.
+    #[inline]
+    pub fn path(&self) -> &[Entity] {
+        &self.input.path
+    }
+
+    /// Returns the [`Target`] of the event, followed by its [`Targets`].
+    #[inline]
+    pub fn targets(&self) -> &[Entity] {
+        &self.input.targets
+    }
+
+    /// Stop the event from propagating to the next ancestor, without despawning it.
+    ///
+    /// The remaining callbacks on the current entity will still run.
+    #[inline]
+    pub fn stop_propagation(&self) {
+        self.input.stop_propagation();
+    }
+
+    /// Stop the event from propagating to the next ancestor, and skip the remaining callbacks on the current entity.
+    #[inline]
+    pub fn stop_immediate_propagation(&self) {
+        self.input.stop_immediate_propagation();
+    }
+
+    #[inline]
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.input.is_propagation_stopped()
+    }
+
+    /// Retrieve an immutable reference to the event data from the query.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the entity is not in the query.
+    #[inline]
+    #[track_caller]
+    pub fn event(&self) -> ROQueryItem<'_, D> {
+        self.get_event().unwrap()
+    }
+
+    /// Retrieve a mutable reference to the event data from the query.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the entity is not in the query.
+    #[inline]
+    #[track_caller]
+    pub fn event_mut(&mut self) -> QueryItem<'_, D> {
+        self.get_event_mut().unwrap()
+    }
+
+    /// Retrieve an immutable reference to the event data from the query.
+    #[inline]
+    pub fn get_event(&self) -> Result<ROQueryItem<'_, D>, bevy_ecs::query::QueryEntityError> {
+        self.query.get(self.input.event_type.id())
+    }
+
+    /// Retrieve a mutable reference to the event data from the query.
+    #[inline]
+    pub fn get_event_mut(&mut self) -> Result<QueryItem<'_, D>, bevy_ecs::query::QueryEntityError> {
+        self.query.get_mut(self.input.event_type.id())
+    }
+
+    /// Returns the entity of the event.
+    #[inline]
+    pub fn id(&self) -> Entity {
+        self.input.event_type.id()
+    }
+
+    #[inline]
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    #[inline]
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct CallbackIdent {
+    key: CallbackKey,
+    fn_entity_contains: fn(EntityRef) -> bool,
+    capture: bool,
+    source: bool,
+}
+
+impl CallbackIdent {
+    pub fn new<T: Listenable>() -> Self {
+        Self {
+            key: CallbackKey::Type(TypeId::of::<T>()),
+            fn_entity_contains: |entity| T::entity_contains(entity),
+            capture: false,
+            source: false,
+        }
+    }
+
+    pub fn key(&self) -> &CallbackKey {
+        &self.key
+    }
+
+    /// Run the callback in the [`Phase::Source`] phase instead of the [`Phase::Bubble`] phase.
+    pub fn with_source(mut self, source: bool) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Returns `true` if this is a source callback.
+    pub fn is_source(&self) -> bool {
+        self.source
+    }
+
+    fn role(&self) -> (bool, bool) {
+        (self.capture, self.source)
+    }
+
+    /// Run the callback in the [`Phase::Capture`] phase instead of the [`Phase::Bubble`] phase.
+    pub fn with_capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Returns `true` if this is a capture callback.
+    pub fn is_capture(&self) -> bool {
+        self.capture
+    }
+
+    /// Returns the `TypeId` of the [`Listenable`] type.
+    pub fn type_id(&self) -> TypeId {
+        match self.key {
+            CallbackKey::Type(type_id) | CallbackKey::Dynamic(type_id, _) => type_id,
+        }
+    }
+
+    pub fn entity_contains(&self, entity: EntityRef) -> bool {
+        (self.fn_entity_contains)(entity)
+            && self
+                .component_ids()
+                .iter()
+                .all(|&id| entity.contains_id(id))
+    }
+}
+
+/// The input of a callback system, describing the event being dispatched.
+///
+/// Callbacks can take this as `In<EventContext>`, or read the same information with a [`Listener`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EventContext {
+    /// The entity of the event.
+    pub event: Entity,
+    pub phase: Phase,
+    /// The [`Target`] of the event.
+    pub original_target: Option<Entity>,
+    /// The entity whose callbacks are currently running.
+    pub current_target: Option<Entity>,
+    /// How many times the event has propagated to reach [`EventContext::current_target`].
+    pub depth: usize,
+}
+
+impl EventContext {
+    /// Returns true if the event is propagated. Ie. it is not the root event.
+    #[inline]
+    pub fn is_propagated(&self) -> bool {
+        self.depth > 0
+    }
+}
+
+/// Returned by a callback to decide if the event keeps propagating.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Propagation {
+    #[default]
+    Continue,
+    /// Stop the event from propagating to the next ancestor, like [`Listener::stop_propagation`].
+    Stop,
+}
+
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+
+pub type CallbackResult = Result<Propagation, BoxedError>;
+
+/// The return type of a callback system.
+///
+/// Implemented for `()`, [`Propagation`], and [`Result`]s of those.
+pub trait CallbackReturn: Send + Sync + 'static {
+    fn into_result(self) -> CallbackResult;
+}
+
+impl CallbackReturn for () {
+    fn into_result(self) -> CallbackResult {
+        Ok(Propagation::Continue)
+    }
+}
+
+impl CallbackReturn for Propagation {
+    fn into_result(self) -> CallbackResult {
+        Ok(self)
+    }
+}
+
+impl<E: Into<BoxedError> + Send + Sync + 'static> CallbackReturn for Result<(), E> {
+    fn into_result(self) -> CallbackResult {
+        self.map(|()| Propagation::Continue).map_err(Into::into)
+    }
+}
+
+impl<E: Into<BoxedError> + Send + Sync + 'static> CallbackReturn for Result<Propagation, E> {
+    fn into_result(self) -> CallbackResult {
+        self.map_err(Into::into)
+    }
+}
+
+pub type BoxedCallbackSystem = BoxedSystem<EventContext, CallbackResult>;
+
+/// Conversion into a callback system, implemented for systems taking `In<EventContext>` and systems without input,
+/// that return a [`CallbackReturn`].
+pub trait IntoCallbackSystem<M>: Send + Sync + 'static {
+    fn into_callback_system(self) -> BoxedCallbackSystem;
+}
+
+/// Pipe the output of a callback into [`CallbackReturn::into_result`], keeping the name of the callback.
+fn into_result_system<R: CallbackReturn>(
+    system: impl System<In = EventContext, Out = R>,
+) -> BoxedCallbackSystem {
+    let name = system.name();
+    let into_result = IntoSystem::into_system(|In(output): In<R>| output.into_result());
+    Box::new(PipeSystem::new(system, into_result, name))
+}
+
+#[doc(hidden)]
+pub struct WithoutInput;
+
+impl<M, R, S> IntoCallbackSystem<(WithoutInput, R, M)> for S
+where
+    R: CallbackReturn,
+    S: IntoSystem<(), R, M> + Send + Sync + 'static,
+{
+    fn into_callback_system(self) -> BoxedCallbackSystem {
+        let system = IntoSystem::into_system(self);
+        let name = system.name();
+        let ignore_input = IntoSystem::into_system(|_: In<EventContext>| {});
+        into_result_system(PipeSystem::new(ignore_input, system, name))
+    }
+}
+
+#[doc(hidden)]
+pub struct WithInput;
+
+impl<M, R, S> IntoCallbackSystem<(WithInput, R, M)> for S
+where
+    R: CallbackReturn,
+    S: IntoSystem<EventContext, R, M> + Send + Sync + 'static,
+{
+    fn into_callback_system(self) -> BoxedCallbackSystem {
+        into_result_system(IntoSystem::into_system(self))
+    }
+}
+
+/// Conversion into the system of a callback, implemented for [`IntoCallbackSystem`]s and [`SharedCallback`]s.
+pub trait IntoCallbackSystemInner<M>: Send + Sync + 'static {
+    fn into_callback_system_inner(self) -> CallbackSystemInner;
+}
+
+impl<M, S: IntoCallbackSystem<M>> IntoCallbackSystemInner<M> for S {
+    fn into_callback_system_inner(self) -> CallbackSystemInner {
+        CallbackSystemInner::Pending(Some(self.into_callback_system()))
+    }
+}
+
+impl IntoCallbackSystemInner<()> for SharedCallback {
+    fn into_callback_system_inner(self) -> CallbackSystemInner {
+        CallbackSystemInner::Shared(self)
+    }
+}
+
+/// A callback system registered once with [`RegisterCallbackExt::register_callback`],
+/// that can be used by many callbacks.
+///
+/// All callbacks using it share a single initialized system.
+/// If the system panics, it's removed from the world, like other systems registered with [`World::register_system`],
+/// and so are the callbacks using it.
+#[derive(Debug, Clone)]
+pub struct SharedCallback {
+    id: SystemId<EventContext, CallbackResult>,
+    name: Cow<'static, str>,
+}
+
+impl SharedCallback {
+    #[inline]
+    pub fn id(&self) -> SystemId<EventContext, CallbackResult> {
+        self.id
+    }
+}
+
+pub trait RegisterCallbackExt {
+    /// Register a callback system once, to use it for many callbacks.
+    ///
+    /// ```ignore
+    /// let block = world.register_callback(block_attack);
+    /// for enemy in enemies {
+    ///     world.entity_mut(enemy).add_callback(On::<Attack>::run(block.clone()));
+    /// }
+    /// ```
+    fn register_callback<M>(&mut self, system: impl IntoCallbackSystem<M>) -> SharedCallback;
+
+    /// Remove a callback system registered with [`RegisterCallbackExt::register_callback`],
+    /// and every callback using it.
+    fn unregister_callback(&mut self, shared: SharedCallback) -> &mut Self;
+}
+
+impl RegisterCallbackExt for World {
+    fn register_callback<M>(&mut self, system: impl IntoCallbackSystem<M>) -> SharedCallback {
+        let system = system.into_callback_system();
+        let name = system.name();
+        SharedCallback {
+            id: self.register_boxed_system(system),
+            name,
+        }
+    }
+
+    fn unregister_callback(&mut self, shared: SharedCallback) -> &mut Self {
+        despawn_shared_callbacks(self, shared.id);
+        let _ = self.remove_system(shared.id);
+        self
+    }
+}
+
+impl RegisterCallbackExt for App {
+    fn register_callback<M>(&mut self, system: impl IntoCallbackSystem<M>) -> SharedCallback {
+        self.world.register_callback(system)
+    }
+
+    fn unregister_callback(&mut self, shared: SharedCallback) -> &mut Self {
+        self.world.unregister_callback(shared);
+        self
+    }
+}
+
+/// Despawn every callback using the shared callback system.
+fn despawn_shared_callbacks(world: &mut World, id: SystemId<EventContext, CallbackResult>) {
+    let uses =
+        |inner: &CallbackSystemInner| matches!(inner, CallbackSystemInner::Shared(s) if s.id == id);
+    let mut callbacks: Vec<CallbackId> = world
+        .query::<(Entity, &CallbackSystemInner)>()
+        .iter(world)
+        .filter(|(_, inner)| uses(inner))
+        .map(|(callback, _)| CallbackId::Entity(callback))
+        .collect();
+    callbacks.extend(
+        world
+            .query::<&EventCallbacks>()
+            .iter(world)
+            .flatten()
+            .filter(|stored| stored.system.as_ref().is_some_and(uses))
+            .map(StoredCallback::id),
+    );
+    for callback in callbacks {
+        despawn_callback(world, callback);
+    }
+}
+
+#[derive(Component)]
+pub enum CallbackSystemInner {
+    Pending(Option<BoxedCallbackSystem>),
+    Ready(BoxedCallbackSystem),
+    /// A system shared by many callbacks, see [`SharedCallback`].
+    Shared(SharedCallback),
+}
+
+pub struct CallbackSystem {
+    inner: CallbackSystemInner,
+    name: Cow<'static, str>,
+}
+
+impl CallbackSystem {
+    pub fn new<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        let inner = CallbackSystemInner::new(system);
+        let name = inner.name();
+        Self { inner, name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn run(&mut self, input: EventContext, world: &mut World) -> CallbackResult {
+        self.inner.run(input, world)
+    }
+}
+
+impl CallbackSystemInner {
+    pub fn new<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        system.into_callback_system_inner()
+    }
+
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            CallbackSystemInner::Pending(system) => system.as_ref().unwrap().name(),
+            CallbackSystemInner::Ready(system) => system.name(),
+            CallbackSystemInner::Shared(shared) => shared.name.clone(),
+        }
+    }
+
+    pub fn run(&mut self, input: EventContext, world: &mut World) -> CallbackResult {
+        if let CallbackSystemInner::Shared(shared) = self {
+            return world
+                .run_system_with_input(shared.id, input)
+                .unwrap_or_else(|error| Err(error.into()));
+        }
+
+        // initialize the system before running it, so it stays usable if it panics
+        if let CallbackSystemInner::Pending(system) = self {
+            let mut system = system.take().unwrap();
+            system.initialize(world);
+            *self = CallbackSystemInner::Ready(system);
+        }
+        let CallbackSystemInner::Ready(system) = self else {
+            unreachable!()
+        };
+        let result = system.run(input, world);
+        system.apply_deferred(world);
+        result
+    }
+}
+
+#[derive(Component)]
+pub struct On<T: Listenable> {
+    ident: CallbackIdent,
+    system: CallbackSystemInner,
+    runs: Option<RemainingRuns>,
+    conditions: Vec<BoxedCondition>,
+    priority: Option<CallbackPriority>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Listenable> On<T> {
+    /// Run the callback when the event reaches the [`Target`], and as it bubbles up from the target to the root.
+    pub fn run<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        Self {
+            marker: PhantomData,
+            ident: CallbackIdent::new::<T>(),
+            system: CallbackSystemInner::new(system),
+            runs: None,
+            conditions: Vec::new(),
+            priority: None,
+        }
+    }
+
+    /// Run the callback as the event goes down from the root to the [`Target`], before any bubble callbacks.
+    pub fn capture<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        Self {
+            marker: PhantomData,
+            ident: CallbackIdent::new::<T>().with_capture(true),
+            system: CallbackSystemInner::new(system),
+            runs: None,
+            conditions: Vec::new(),
+            priority: None,
+        }
+    }
+
+    /// Run the callback when the event is dispatched to its [`Source`], after it was dispatched to its [`Target`].
+    pub fn from_source<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        Self {
+            ident: CallbackIdent::new::<T>().with_source(true),
+            ..Self::run(system)
+        }
+    }
+
+    /// Run the callback only once, then remove it.
+    pub fn once<M>(system: impl IntoCallbackSystemInner<M>) -> Self {
+        Self::times(1, system)
+    }
+
+    /// Run the callback `n` times, then remove it.
+    ///
+    /// Every run counts, also the runs for propagated events.
+    pub fn times<M>(n: u32, system: impl IntoCallbackSystemInner<M>) -> Self {
+        Self {
+            runs: Some(RemainingRuns(n)),
+            ..Self::run(system)
+        }
+    }
+
+    /// Only run the callback if the condition returns `true`.
+    ///
+    /// The condition can read the event with a [`Listener`]. Adding multiple conditions runs the callback
+    /// only if all of them return `true`.
+    pub fn run_if<M>(mut self, condition: impl Condition<M>) -> Self {
+        self.conditions
+            .push(Box::new(IntoSystem::into_system(condition)));
+        self
+    }
+
+    /// Set the [`CallbackPriority`] of the callback. Callbacks with a higher priority run first.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(CallbackPriority(priority));
+        self
+    }
+
+    /// Only run the callback for events that also have all of these components.
+    ///
+    /// Use [`DynamicEvent`] to match the events by their components alone:
+    /// ```ignore
+    /// world.add_callback(On::<DynamicEvent>::run(callback).with_component_ids([id]));
+    /// ```
+    pub fn with_component_ids(mut self, ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        self.ident = self.ident.with_component_ids(ids);
+        self
+    }
+}
+
+pub trait IntoCallback<T: Listenable, M>: Send + Sync + 'static {
+    fn into_callback(self) -> On<T>;
+}
+
+impl<T: Listenable> IntoCallback<T, ()> for On<T> {
+    #[inline]
+    fn into_callback(self) -> On<T> {
+        self
+    }
+}
+
+impl<T: Listenable> IntoCallback<T, ()> for CallbackSystemInner {
+    #[inline]
+    fn into_callback(self) -> On<T> {
+        On {
+            ident: CallbackIdent::new::<T>(),
+            system: self,
+            runs: None,
+            conditions: Vec::new(),
+            priority: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M, T: Listenable, S: IntoCallbackSystemInner<M>> IntoCallback<T, M> for S {
+    #[inline]
+    fn into_callback(self) -> On<T> {
+        On::run(self)
+    }
+}
+
+/// The number of times a callback will still run before it's removed, see [`On::times`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemainingRuns(pub u32);
+
+/// The order callbacks on the same entity, or global callbacks, run in.
+///
+/// Callbacks with a higher priority run first, callbacks with the same priority run in the order they were added.
+/// Callbacks without this component have a priority of 0.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallbackPriority(pub i32);
+
+/// Enables or disables a callback. Callbacks without this component are enabled.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enabled(pub bool);
+
+/// The initialized run conditions of a callback, see [`On::run_if`].
+#[derive(Component)]
+pub struct CallbackConditions(Vec<BoxedCondition>);
+
+impl CallbackConditions {
+    /// Returns `true` if all conditions return `true`.
+    ///
+    /// The conditions are taken out of the callback to run them, the callback itself stays in place.
+    fn run(world: &mut World, callback: CallbackId) -> bool {
+        let conditions = match callback {
+            CallbackId::Entity(entity) => world
+                .get_mut::<CallbackConditions>(entity)
+                .map(|mut c| mem::take(&mut c.0)),
+            CallbackId::Stored(_) => {
+                stored_callback_mut(world, callback).map(|s| mem::take(&mut s.conditions))
+            }
+        };
+        let Some(mut conditions) = conditions else {
+            return true;
+        };
+        let run = conditions
+            .iter_mut()
+            .all(|condition| condition.run_readonly((), world));
+        match callback {
+            CallbackId::Entity(entity) => {
+                if let Some(mut c) = world.get_mut::<CallbackConditions>(entity) {
+                    c.0 = conditions;
+                }
+            }
+            CallbackId::Stored(_) => {
+                if let Some(stored) = stored_callback_mut(world, callback) {
+                    stored.conditions = conditions;
+                }
+            }
+        }
+        run
+    }
+}
+
+/// A handle to a callback, returned by [`AddCallbackExt::add_callback`].
+///
+/// Use it to remove the callback again with [`RemoveCallbackExt::remove_callback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallbackId {
+    /// A callback spawned as an entity.
+    Entity(Entity),
+    /// A callback stored in the [`EventCallbacks`] of the entity it was added to, numbered by the [`CallbackIndex`].
+    Stored(u64),
+}
+
+impl CallbackId {
+    /// Returns the entity of the callback, or `None` if it's stored in [`EventCallbacks`].
+    #[inline]
+    pub fn entity(&self) -> Option<Entity> {
+        match *self {
+            CallbackId::Entity(entity) => Some(entity),
+            CallbackId::Stored(_) => None,
+        }
+    }
+}
+
+/// Add a callback to the world and to the [`CallbackIndex`].
+///
+/// If the callback has an owner, it will only run if the owner was the [`Target`] of the event.
+/// The callback is inserted into `callback` if it was already spawned. Otherwise it's stored according to the
+/// [`CallbackStorage`], unless it's a subscription of `subscriber` to the owner.
+fn insert_callback<T: Listenable>(
+    world: &mut World,
+    callback: Option<Entity>,
+    owner: Option<Entity>,
+    subscriber: Option<Entity>,
+    on: On<T>,
+) -> CallbackId {
+    let ids = on.ident.component_ids().iter().copied();
+    let ident = match owner {
+        Some(_) if on.ident.source => CallbackIdent::new::<(Source, T)>()
+            .with_source(true)
+            .with_component_ids(ids),
+        Some(_) => CallbackIdent::new::<(Target, T)>()
+            .with_capture(on.ident.capture)
+            .with_component_ids(ids),
+        None => on.ident.clone(),
+    };
+    let mut conditions = on.conditions;
+    for condition in &mut conditions {
+        condition.initialize(world);
+    }
+    let priority = on.priority.unwrap_or_default();
+    let storage = world.get_resource::<CallbackStorage>().copied();
+    let callback = match (callback, owner, subscriber, storage) {
+        (Some(callback), ..) => callback,
+        (None, Some(owner), None, Some(CallbackStorage::Component)) => {
+            let mut index = world.get_resource_or_insert_with(CallbackIndex::default);
+            let id = index.allocate_stored();
+            index.insert(id, Some(owner), ident.clone(), priority);
+            let CallbackId::Stored(n) = id else {
+                unreachable!()
+            };
+            world
+                .entity_mut(owner)
+                .entry::<EventCallbacks>()
+                .or_default()
+                .0
+                .push(StoredCallback {
+                    id: n,
+                    ident,
+                    system: Some(on.system),
+                    runs: on.runs,
+                    conditions,
+                    priority,
+                    enabled: true,
+                });
+            return id;
+        }
+        (None, ..) => world.spawn_empty().id(),
+    };
+    let mut entity = world.entity_mut(callback);
+    entity.insert((ident.clone(), on.system));
+    if let Some(runs) = on.runs {
+        entity.insert(runs);
+    }
+    if !conditions.is_empty() {
+        entity.insert(CallbackConditions(conditions));
+    }
+    if let Some(priority) = on.priority {
+        entity.insert(priority);
+    }
+    match (owner, subscriber) {
+        (Some(publisher), Some(subscriber)) => {
+            world.entity_mut(callback).insert(Subscription {
+                publisher,
+                subscriber,
+            });
+            world
+                .entity_mut(publisher)
+                .entry::<Subscribers>()
+                .or_default()
+                .0
+                .push(callback);
+            world
+                .entity_mut(subscriber)
+                .entry::<Subscriptions>()
+                .or_default()
+                .0
+                .push(callback);
+        }
+        (Some(owner), None) => {
+            world.entity_mut(owner).add_child(callback);
+        }
+        _ => {}
+    }
+    let id = CallbackId::Entity(callback);
+    world
+        .get_resource_or_insert_with(CallbackIndex::default)
+        .insert(id, owner, ident, priority);
+    id
+}
+
+pub trait AddCallbackExt {
+    /// Run a system when the event matching `T` is triggered.
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId;
+}
+
+impl AddCallbackExt for World {
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId {
+        insert_callback(self, None, None, None, callback.into_callback())
+    }
+}
+
+impl AddCallbackExt for App {
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId {
+        self.world.add_callback(callback)
+    }
+}
+
+impl<'w, 's> AddCallbackExt for Commands<'w, 's> {
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId {
+        let entity = self.spawn_empty().id();
+        self.add(move |world: &mut World| {
+            insert_callback(world, Some(entity), None, None, callback.into_callback());
+        });
+        CallbackId::Entity(entity)
+    }
+}
+
+impl<'w> AddCallbackExt for EntityWorldMut<'w> {
     /// Run a system when the event matching `T` is triggered.
     ///
     /// This will only run the callback system if this entity was the [`Target`] of the event.
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self {
-        let callback = self.world_scope(|world| {
-            world
-                .spawn((
-                    CallbackIdent::new::<(Target, T)>(),
-                    callback.into_bundle().1,
-                ))
-                .id()
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId {
+        let owner = self.id();
+        self.world_scope(|world| {
+            insert_callback(world, None, Some(owner), None, callback.into_callback())
+        })
+    }
+}
+
+impl<'a> AddCallbackExt for EntityCommands<'a> {
+    /// Run a system when the event matching `T` is triggered.
+    ///
+    /// This will only run the callback system if this entity was the [`Target`] of the event.
+    ///
+    /// The callback is always spawned as a child, regardless of the [`CallbackStorage`].
+    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> CallbackId {
+        let owner = self.id();
+        let entity = self.commands().spawn_empty().id();
+        self.commands().add(move |world: &mut World| {
+            if world.get_entity(owner).is_some() {
+                let on = callback.into_callback();
+                insert_callback(world, Some(entity), Some(owner), None, on);
+            } else {
+                world.despawn(entity);
+            }
+        });
+        CallbackId::Entity(entity)
+    }
+}
+
+/// Despawn a callback, or remove it from the [`EventCallbacks`] it's stored in, and remove it from the [`CallbackIndex`].
+fn despawn_callback(world: &mut World, callback: CallbackId) {
+    if let Some(owner) = stored_owner(world, callback) {
+        if let Some(mut callbacks) = world.get_mut::<EventCallbacks>(owner) {
+            callbacks.0.retain(|c| c.id() != callback);
+        }
+    }
+    if let Some(mut index) = world.get_resource_mut::<CallbackIndex>() {
+        index.remove(callback);
+    }
+    let CallbackId::Entity(entity) = callback else {
+        return;
+    };
+    if let Some(&subscription) = world.get::<Subscription>(entity) {
+        subscription.unlink(world, entity);
+    }
+    if let Some(entity) = world.get_entity_mut(entity) {
+        entity.despawn_recursive();
+    }
+}
+
+/// Despawn the callbacks of `owner`, or the global callbacks if it's `None`, that match the filter.
+fn despawn_callbacks(
+    world: &mut World,
+    owner: Option<Entity>,
+    filter: impl Fn(&CallbackIdent) -> bool,
+) {
+    let callbacks: Vec<CallbackId> = match owner {
+        Some(owner) => world
+            .get::<Children>(owner)
+            .into_iter()
+            .flatten()
+            .chain(world.get::<Subscriptions>(owner).into_iter().flatten())
+            .filter(|&&child| world.get::<CallbackIdent>(child).is_some_and(&filter))
+            .map(|&child| CallbackId::Entity(child))
+            .chain(
+                world
+                    .get::<EventCallbacks>(owner)
+                    .into_iter()
+                    .flatten()
+                    .filter(|stored| filter(&stored.ident))
+                    .map(StoredCallback::id),
+            )
+            .collect(),
+        None => world
+            .query_filtered::<(Entity, &CallbackIdent), (Without<Parent>, Without<Subscription>)>()
+            .iter(world)
+            .filter(|(_, ident)| filter(ident))
+            .map(|(callback, _)| CallbackId::Entity(callback))
+            .collect(),
+    };
+    for callback in callbacks {
+        despawn_callback(world, callback);
+    }
+}
+
+pub trait RemoveCallbackExt {
+    /// Remove a callback added with [`AddCallbackExt::add_callback`].
+    fn remove_callback(&mut self, callback: CallbackId) -> &mut Self;
+
+    /// Remove the callbacks listening to `T`.
+    ///
+    /// On [`World`] and [`Commands`] this removes the global callbacks,
+    /// on entities this removes the callbacks of the entity.
+    fn remove_callbacks<T: Listenable>(&mut self) -> &mut Self;
+
+    /// Remove all callbacks.
+    ///
+    /// On [`World`] and [`Commands`] this removes the global callbacks,
+    /// on entities this removes the callbacks of the entity.
+    fn clear_callbacks(&mut self) -> &mut Self;
+}
+
+impl RemoveCallbackExt for World {
+    /// Remove a callback added with [`AddCallbackExt::add_callback`], global or not.
+    fn remove_callback(&mut self, callback: CallbackId) -> &mut Self {
+        let exists = match callback {
+            CallbackId::Entity(entity) => self.get::<CallbackIdent>(entity).is_some(),
+            CallbackId::Stored(_) => stored_owner(self, callback).is_some(),
+        };
+        if exists {
+            despawn_callback(self, callback);
+        }
+        self
+    }
+
+    fn remove_callbacks<T: Listenable>(&mut self) -> &mut Self {
+        despawn_callbacks(self, None, |ident| ident.type_id() == TypeId::of::<T>());
+        self
+    }
+
+    fn clear_callbacks(&mut self) -> &mut Self {
+        despawn_callbacks(self, None, |_| true);
+        self
+    }
+}
+
+impl<'w, 's> RemoveCallbackExt for Commands<'w, 's> {
+    /// Remove a callback added with [`AddCallbackExt::add_callback`], global or not.
+    fn remove_callback(&mut self, callback: CallbackId) -> &mut Self {
+        self.add(move |world: &mut World| {
+            world.remove_callback(callback);
+        });
+        self
+    }
+
+    fn remove_callbacks<T: Listenable>(&mut self) -> &mut Self {
+        self.add(|world: &mut World| {
+            world.remove_callbacks::<T>();
+        });
+        self
+    }
+
+    fn clear_callbacks(&mut self) -> &mut Self {
+        self.add(|world: &mut World| {
+            world.clear_callbacks();
+        });
+        self
+    }
+}
+
+impl<'w> RemoveCallbackExt for EntityWorldMut<'w> {
+    /// Remove a callback of this entity. Does nothing if the callback belongs to another entity.
+    fn remove_callback(&mut self, callback: CallbackId) -> &mut Self {
+        let owner = self.id();
+        self.world_scope(|world| {
+            let owned = match callback {
+                CallbackId::Entity(entity) => world.get_entity(entity).is_some_and(|entity| {
+                    entity.contains::<CallbackIdent>()
+                        && (entity.get::<Parent>().map(Parent::get) == Some(owner)
+                            || entity.get::<Subscription>().map(|s| s.subscriber) == Some(owner))
+                }),
+                CallbackId::Stored(_) => stored_owner(world, callback) == Some(owner),
+            };
+            if owned {
+                despawn_callback(world, callback);
+            }
+        });
+        self
+    }
+
+    fn remove_callbacks<T: Listenable>(&mut self) -> &mut Self {
+        let owner = self.id();
+        self.world_scope(|world| {
+            despawn_callbacks(world, Some(owner), |ident| {
+                ident.type_id() == TypeId::of::<(Target, T)>()
+                    || ident.type_id() == TypeId::of::<(Source, T)>()
+            });
         });
-        self.add_child(callback);
         self
     }
-}
 
-impl<'a> AddCallbackExt for EntityCommands<'a> {
-    /// Run a system when the event matching `T` is triggered.
-    ///
-    /// This will only run the callback system if this entity was the [`Target`] of the event.
-    fn add_callback<T: Listenable, M>(&mut self, callback: impl IntoCallback<T, M>) -> &mut Self {
-        self.add(move |mut entity: EntityWorldMut| {
-            entity.add_callback(callback);
-        })
+    fn clear_callbacks(&mut self) -> &mut Self {
+        let owner = self.id();
+        self.world_scope(|world| despawn_callbacks(world, Some(owner), |_| true));
+        self
+    }
+}
+
+impl<'a> RemoveCallbackExt for EntityCommands<'a> {
+    /// Remove a callback of this entity. Does nothing if the callback belongs to another entity.
+    fn remove_callback(&mut self, callback: CallbackId) -> &mut Self {
+        self.add(move |mut entity: EntityWorldMut| {
+            entity.remove_callback(callback);
+        })
+    }
+
+    fn remove_callbacks<T: Listenable>(&mut self) -> &mut Self {
+        self.add(|mut entity: EntityWorldMut| {
+            entity.remove_callbacks::<T>();
+        })
+    }
+
+    fn clear_callbacks(&mut self) -> &mut Self {
+        self.add(|mut entity: EntityWorldMut| {
+            entity.clear_callbacks();
+        })
+    }
+}
+
+// this tests if events are propagated up the tree and if it stops when the event is despawned
+#[test]
+fn test_propagate_events() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Stop;
+
+    #[derive(Component)]
+    struct Marker;
+
+    fn callback(
+        mut commands: Commands,
+        input: Listener<(Entity, &Target)>,
+        stop: Query<(), With<Stop>>,
+    ) {
+        let (event, &Target(original)) = input.event();
+        let target = input.current_target().unwrap();
+        assert_eq!(input.original_target(), Some(original));
+        assert_eq!(input.path()[input.depth()], target);
+        commands.entity(target).insert(Marker);
+        if dbg!(stop.contains(target)) {
+            commands.entity(event).despawn();
+        }
+    }
+
+    let (mut world, mut schedule) = test_world();
+
+    let mut entities = Vec::new();
+    for i in 0..10 {
+        let entity = world.spawn_empty().id();
+        world
+            .entity_mut(entity)
+            .add_callback::<TestEvent, _>(callback);
+        if i > 0 {
+            world.entity_mut(entity).add_child(entities[i - 1]);
+        }
+        if i == 5 {
+            world.entity_mut(entity).insert(Stop);
+        }
+        entities.push(entity);
+    }
+
+    send_event(&mut world, (TestEvent, Target(entities[0])));
+    schedule.run(&mut world);
+
+    // propagating doesn't send any new events
+    assert_eq!(world.resource::<EventEntities>().len(), 1);
+
+    for (n, entity) in entities.into_iter().enumerate() {
+        dbg!(n, world.entity(entity).contains::<Marker>());
+        if n > 5 {
+            assert!(!world.entity(entity).contains::<Marker>());
+        } else {
+            assert!(world.entity(entity).contains::<Marker>());
+        }
+    }
+}
+
+// this tests if `stop_propagation` and `stop_immediate_propagation` stop the event without despawning it
+#[test]
+fn test_stop_propagation() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component, Clone, Copy, PartialEq)]
+    enum Stop {
+        Propagation,
+        Immediate,
+    }
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<Entity>);
+
+    fn callback(input: Listener<&Target>, stop: Query<&Stop>, mut visited: ResMut<Visited>) {
+        let target = input.current_target().unwrap();
+        visited.0.push(target);
+        match stop.get(target) {
+            Ok(Stop::Propagation) => input.stop_propagation(),
+            Ok(Stop::Immediate) => input.stop_immediate_propagation(),
+            Err(_) => {}
+        }
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+
+    let mut run = |world: &mut World, stop: Stop| {
+        world.resource_mut::<Visited>().0.clear();
+        let parent = world.spawn(Stop::Propagation).id();
+        let entity = world.spawn(stop).set_parent(parent).id();
+        world
+            .entity_mut(parent)
+            .add_callback::<TestEvent, _>(callback);
+        let mut entity_mut = world.entity_mut(entity);
+        entity_mut.add_callback::<TestEvent, _>(callback);
+        entity_mut.add_callback::<TestEvent, _>(callback);
+        let event = send_event(world, (TestEvent, Target(entity))).id();
+        schedule.run(world);
+
+        assert!(world.get_entity(event).is_some());
+        assert!(!world.resource::<Visited>().0.contains(&parent));
+        world.resource::<Visited>().0.len()
+    };
+
+    assert_eq!(run(&mut world, Stop::Propagation), 2);
+    assert_eq!(run(&mut world, Stop::Immediate), 1);
+}
+
+// this tests if capture callbacks run from the root down to the target before the bubble callbacks
+#[test]
+fn test_capture_phase() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Intercept;
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<(Entity, Phase, bool)>);
+
+    fn callback<const CAPTURE: bool>(
+        input: Listener<&Target>,
+        intercept: Query<(), With<Intercept>>,
+        mut visited: ResMut<Visited>,
+    ) {
+        let target = input.current_target().unwrap();
+        visited.0.push((target, input.phase(), CAPTURE));
+        if CAPTURE && intercept.contains(target) {
+            input.stop_propagation();
+        }
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+
+    let root = world.spawn_empty().id();
+    let middle = world.spawn_empty().set_parent(root).id();
+    let leaf = world.spawn_empty().set_parent(middle).id();
+    for entity in [root, middle, leaf] {
+        let mut entity = world.entity_mut(entity);
+        entity.add_callback(On::<TestEvent>::run(callback::<false>));
+        entity.add_callback(On::<TestEvent>::capture(callback::<true>));
+    }
+
+    send_event(&mut world, (TestEvent, Target(leaf)));
+    schedule.run(&mut world);
+    assert_eq!(
+        world.resource::<Visited>().0,
+        [
+            (root, Phase::Capture, true),
+            (middle, Phase::Capture, true),
+            (leaf, Phase::Target, true),
+            (leaf, Phase::Target, false),
+            (middle, Phase::Bubble, false),
+            (root, Phase::Bubble, false),
+        ]
+    );
+
+    // a capture callback is able to intercept the event before it reaches the target
+    world.resource_mut::<Visited>().0.clear();
+    world.entity_mut(middle).insert(Intercept);
+    send_event(&mut world, (TestEvent, Target(leaf)));
+    schedule.run(&mut world);
+    assert_eq!(
+        world.resource::<Visited>().0,
+        [(root, Phase::Capture, true), (middle, Phase::Capture, true)]
+    );
+}
+
+// this tests if events propagate along custom relations
+#[test]
+fn test_traversal() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Attack;
+
+    impl Listenable for Attack {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Component)]
+    struct Wielder(Entity);
+
+    impl Traversal for Wielder {
+        fn traverse(&self) -> Option<Entity> {
+            Some(self.0)
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<Entity>);
+
+    fn callback(input: Listener<&Target>, mut visited: ResMut<Visited>) {
+        visited.0.push(input.current_target().unwrap());
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+
+    let player = world.spawn_empty().id();
+    let parent = world.spawn_empty().id();
+    let sword = world.spawn(Wielder(player)).set_parent(parent).id();
+    for entity in [player, parent, sword] {
+        world
+            .entity_mut(entity)
+            .add_callback(On::<Attack>::run(callback));
     }
+
+    // propagates along `Parent` by default
+    send_event(&mut world, (Attack, Target(sword)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [sword, parent]);
+
+    // the traversal can be overridden per event
+    world.resource_mut::<Visited>().0.clear();
+    send_event(
+        &mut world,
+        (Attack, Target(sword), EventTraversal::new::<Wielder>()),
+    );
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [sword, player]);
+
+    // or per `Listenable` type
+    world.resource_mut::<Visited>().0.clear();
+    world.add_traversal::<Attack, Wielder>();
+    send_event(&mut world, (Attack, Target(sword)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [sword, player]);
+
+    // cycles stop propagating
+    world.resource_mut::<Visited>().0.clear();
+    world.entity_mut(player).insert(Wielder(sword));
+    send_event(&mut world, (Attack, Target(sword)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [sword, player]);
 }
 
-// this tests if events are propagated up the tree and if it stops when the event is despawned
+// this tests if callbacks can be removed by id, by type or all at once
 #[test]
-fn test_propagate_events() {
+fn test_remove_callbacks() {
+    use bevy_ecs::system::CommandQueue;
     use bevy_event_entities_core::send_event;
 
     #[derive(Component)]
-    struct Stop;
+    struct A;
 
     #[derive(Component)]
-    struct Marker;
+    struct B;
+
+    impl Listenable for A {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    impl Listenable for B {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    fn count(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Count>();
+
+    let mut run = |world: &mut World, target: Entity| {
+        world.resource_mut::<Count>().0 = 0;
+        send_event(world, (A, B, Target(target)));
+        schedule.run(world);
+        world.resource::<Count>().0
+    };
+
+    let entity = world.spawn_empty().id();
+    let global_a = world.add_callback::<A, _>(count);
+    world.add_callback::<B, _>(count);
+    let mut entity_mut = world.entity_mut(entity);
+    let entity_a = entity_mut.add_callback::<A, _>(count);
+    entity_mut.add_callback::<A, _>(count);
+    entity_mut.add_callback::<B, _>(count);
+    assert_eq!(run(&mut world, entity), 5);
+
+    // removing a callback of another entity does nothing
+    world.spawn_empty().remove_callback(entity_a);
+    assert_eq!(run(&mut world, entity), 5);
+
+    world.entity_mut(entity).remove_callback(entity_a);
+    world.remove_callback(global_a);
+    assert_eq!(run(&mut world, entity), 3);
+
+    world.entity_mut(entity).remove_callbacks::<A>();
+    assert_eq!(run(&mut world, entity), 2);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+    commands.entity(entity).clear_callbacks();
+    commands.remove_callbacks::<B>();
+    queue.apply(&mut world);
+    assert_eq!(run(&mut world, entity), 0);
+    assert!(world.resource::<CallbackIndex>().is_empty());
+    assert!(world
+        .get::<bevy_hierarchy::Children>(entity)
+        .map_or(true, |children| children.is_empty()));
+}
+
+// this tests if callbacks with a limited number of runs are removed after running out
+#[test]
+fn test_limited_callbacks() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    fn count(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Count>();
+
+    let parent = world.spawn_empty().id();
+    let child = world.spawn_empty().set_parent(parent).id();
+    let once = world
+        .entity_mut(child)
+        .add_callback(On::<TestEvent>::once(count));
+    // runs for the propagated events too
+    let times = world.add_callback(On::<TestEvent>::times(3, count));
+
+    let mut run = |world: &mut World| {
+        world.resource_mut::<Count>().0 = 0;
+        send_event(world, (TestEvent, Target(child)));
+        schedule.run(world);
+        world.resource::<Count>().0
+    };
+
+    assert_eq!(run(&mut world), 3);
+    assert!(world.get_entity(once.entity().unwrap()).is_none());
+    assert_eq!(
+        world.get::<RemainingRuns>(times.entity().unwrap()),
+        Some(&RemainingRuns(1))
+    );
+
+    assert_eq!(run(&mut world), 1);
+    assert!(world.get_entity(times.entity().unwrap()).is_none());
+    assert!(world.resource::<CallbackIndex>().is_empty());
+
+    assert_eq!(run(&mut world), 0);
+}
+
+// this tests if disabled callbacks and callbacks with unmet conditions are skipped
+#[test]
+fn test_callback_conditions() {
+    use bevy_event_entities_core::send_event;
 
     #[derive(Component)]
-    struct TestEvent;
+    struct TestEvent(u32);
 
     impl Listenable for TestEvent {
         fn entity_contains(entity: EntityRef) -> bool {
@@ -568,48 +2374,544 @@ fn test_propagate_events() {
         }
     }
 
-    fn callback(
-        mut commands: Commands,
-        input: Listener<(Entity, &Target)>,
-        stop: Query<(), With<Stop>>,
-    ) {
-        let (event, target) = input.event();
-        commands.entity(target.0).insert(Marker);
-        if dbg!(stop.contains(target.0)) {
-            commands.entity(event).despawn();
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    fn count(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+
+    fn is_even(input: Listener<&TestEvent>) -> bool {
+        input.event().0 % 2 == 0
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Count>();
+
+    let entity = world.spawn_empty().id();
+    world
+        .entity_mut(entity)
+        .add_callback(On::<TestEvent>::run(count).run_if(is_even));
+    let times = world
+        .add_callback(On::<TestEvent>::times(1, count).run_if(|count: Res<Count>| count.0 > 0));
+
+    let mut run = |world: &mut World, n| {
+        world.resource_mut::<Count>().0 = 0;
+        send_event(world, (TestEvent(n), Target(entity)));
+        schedule.run(world);
+        world.resource::<Count>().0
+    };
+
+    // skipped runs don't count towards the limit
+    assert_eq!(run(&mut world, 1), 0);
+    assert!(world.get_entity(times.entity().unwrap()).is_some());
+    assert_eq!(run(&mut world, 2), 2);
+    assert!(world.get_entity(times.entity().unwrap()).is_none());
+    assert_eq!(run(&mut world, 3), 0);
+
+    let callback = world.get::<bevy_hierarchy::Children>(entity).unwrap()[0];
+    world.entity_mut(callback).insert(Enabled(false));
+    assert_eq!(run(&mut world, 4), 0);
+    world.entity_mut(callback).insert(Enabled(true));
+    assert_eq!(run(&mut world, 4), 1);
+}
+
+// this tests if callbacks run in the order of their priority
+#[test]
+fn test_callback_priority() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    impl Listenable for A {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    impl Listenable for B {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
         }
     }
 
-    let mut world = World::new();
+    #[derive(Resource, Default)]
+    struct Order(Vec<u32>);
 
-    world.init_resource::<EventEntities>();
-    let mut schedule = Schedule::default();
-    schedule.add_systems(event_listener_system_configs());
+    fn push<const N: u32>(mut order: ResMut<Order>) {
+        order.0.push(N);
+    }
 
-    let mut entities = Vec::new();
-    for i in 0..10 {
-        let entity = world
-            .spawn_empty()
-            .add_callback::<TestEvent, _>(callback)
-            .id();
-        if i > 0 {
-            world.entity_mut(entity).add_child(entities[i - 1]);
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Order>();
+
+    let entity = world.spawn_empty().id();
+    let mut entity_mut = world.entity_mut(entity);
+    let first = entity_mut.add_callback(On::<A>::run(push::<3>));
+    entity_mut.add_callback(On::<B>::run(push::<2>).priority(5));
+    let last = entity_mut.add_callback(On::<A>::run(push::<4>));
+    entity_mut.add_callback(On::<A>::run(push::<1>).priority(10));
+    world.add_callback(On::<A>::run(push::<6>).priority(-1));
+    world.add_callback(On::<B>::run(push::<5>));
+
+    let mut run = |world: &mut World| {
+        world.resource_mut::<Order>().0.clear();
+        send_event(world, (A, B, Target(entity)));
+        schedule.run(world);
+        mem::take(&mut world.resource_mut::<Order>().0)
+    };
+
+    assert_eq!(run(&mut world), [1, 2, 3, 4, 5, 6]);
+
+    // changing the priority is picked up by the index
+    world
+        .entity_mut(last.entity().unwrap())
+        .insert(CallbackPriority(20));
+    assert_eq!(run(&mut world), [4, 1, 2, 3, 5, 6]);
+    world
+        .entity_mut(last.entity().unwrap())
+        .remove::<CallbackPriority>();
+    assert_eq!(run(&mut world), [1, 2, 3, 4, 5, 6]);
+
+    // a callback keeps its place among the callbacks with the same priority
+    world
+        .entity_mut(first.entity().unwrap())
+        .insert(CallbackPriority(20));
+    assert_eq!(run(&mut world), [3, 1, 2, 4, 5, 6]);
+    world
+        .entity_mut(first.entity().unwrap())
+        .remove::<CallbackPriority>();
+    assert_eq!(run(&mut world), [1, 2, 3, 4, 5, 6]);
+}
+
+// this tests if callbacks can take the `EventContext` as input
+#[test]
+fn test_event_context() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Resource, Default)]
+    struct Contexts(Vec<EventContext>);
+
+    fn callback(In(context): In<EventContext>, listener: Listener, mut contexts: ResMut<Contexts>) {
+        assert_eq!(context, listener.context());
+        contexts.0.push(context);
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Contexts>();
+
+    let parent = world.spawn_empty().id();
+    let child = world.spawn_empty().set_parent(parent).id();
+    world
+        .entity_mut(parent)
+        .add_callback(On::<TestEvent>::capture(callback));
+    world
+        .entity_mut(child)
+        .add_callback::<TestEvent, _>(callback);
+
+    let event = send_event(&mut world, (TestEvent, Target(child))).id();
+    schedule.run(&mut world);
+
+    let context = |phase, current_target, depth| EventContext {
+        event,
+        phase,
+        original_target: Some(child),
+        current_target: Some(current_target),
+        depth,
+    };
+    assert_eq!(
+        world.resource::<Contexts>().0,
+        [
+            context(Phase::Capture, parent, 1),
+            context(Phase::Target, child, 0),
+        ]
+    );
+    // the input stays in the world, reset to its default
+    assert_eq!(world.resource::<ListenerInput>().context().depth, 0);
+    assert!(world.resource::<ListenerInput>().path.is_empty());
+}
+
+// this tests if the values returned by callbacks are acted on
+#[test]
+fn test_callback_return() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Stop;
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<Entity>);
+
+    fn visit(input: Listener, mut visited: ResMut<Visited>) {
+        visited.0.push(input.current_target().unwrap());
+    }
+
+    fn stop(input: Listener, stop: Query<(), With<Stop>>) -> Propagation {
+        match stop.contains(input.current_target().unwrap()) {
+            true => Propagation::Stop,
+            false => Propagation::Continue,
         }
-        if i == 5 {
-            world.entity_mut(entity).insert(Stop);
+    }
+
+    fn fail(In(context): In<EventContext>) -> Result<(), String> {
+        match context.depth {
+            0 => Err("failed at the target".into()),
+            _ => Ok(()),
         }
-        entities.push(entity);
     }
 
-    send_event(&mut world, (TestEvent, Target(entities[0])));
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+    world.insert_resource(EventErrorHandler::Collect);
+
+    let root = world.spawn_empty().id();
+    let middle = world.spawn(Stop).set_parent(root).id();
+    let leaf = world.spawn_empty().set_parent(middle).id();
+    for entity in [root, middle, leaf] {
+        let mut entity = world.entity_mut(entity);
+        entity.add_callback::<TestEvent, _>(visit);
+        entity.add_callback::<TestEvent, _>(stop);
+        entity.add_callback::<TestEvent, _>(fail);
+    }
+
+    send_event(&mut world, (TestEvent, Target(leaf)));
     schedule.run(&mut world);
 
-    for (n, entity) in entities.into_iter().enumerate() {
-        dbg!(n, world.entity(entity).contains::<Marker>());
-        if n > 5 {
-            assert!(!world.entity(entity).contains::<Marker>());
-        } else {
-            assert!(world.entity(entity).contains::<Marker>());
+    assert_eq!(world.resource::<Visited>().0, [leaf, middle]);
+    let errors: Vec<_> = world.resource_mut::<EventErrors>().drain().collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].context.current_target, Some(leaf));
+    assert_eq!(errors[0].error.to_string(), "failed at the target");
+}
+
+// this tests if panicking callbacks are handled according to the `CallbackPanicPolicy`
+#[test]
+fn test_callback_panic_policy() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    fn count(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+
+    fn buggy() {
+        panic!("oops");
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Count>();
+
+    let entity = world.spawn_empty().id();
+    let mut entity_mut = world.entity_mut(entity);
+    let buggy = entity_mut.add_callback::<TestEvent, _>(buggy);
+    entity_mut.add_callback::<TestEvent, _>(count);
+
+    let mut run = |world: &mut World| {
+        world.resource_mut::<Count>().0 = 0;
+        send_event(world, (TestEvent, Target(entity)));
+        schedule.run(world);
+        world.resource::<Count>().0
+    };
+
+    world.insert_resource(CallbackPanicPolicy::LogAndContinue);
+    assert_eq!(run(&mut world), 1);
+    assert_eq!(run(&mut world), 1);
+    assert_eq!(world.resource::<CallbackPanics>().len(), 2);
+
+    world.insert_resource(CallbackPanicPolicy::DisableCallback);
+    assert_eq!(run(&mut world), 1);
+    assert_eq!(run(&mut world), 1);
+    let panics: Vec<_> = world.resource_mut::<CallbackPanics>().drain().collect();
+    assert_eq!(panics.len(), 3);
+    assert_eq!(panics[2].callback, buggy);
+    assert_eq!(panics[2].message.as_deref(), Some("oops"));
+    assert!(!world
+        .entity(buggy.entity().unwrap())
+        .contains::<CallbackSystemInner>());
+}
+
+// this tests if a shared callback system is reused by all callbacks using it
+#[test]
+fn test_shared_callbacks() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Resource, Default)]
+    struct Runs(Vec<usize>);
+
+    // the local is shared by all callbacks
+    fn callback(mut count: Local<usize>, mut runs: ResMut<Runs>) {
+        *count += 1;
+        runs.0.push(*count);
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Runs>();
+
+    let shared = world.register_callback(callback);
+    let root = world.spawn_empty().id();
+    let child = world.spawn_empty().set_parent(root).id();
+    for entity in [root, child] {
+        world
+            .entity_mut(entity)
+            .add_callback(On::<TestEvent>::run(shared.clone()));
+    }
+    world.add_callback(On::<TestEvent>::once(shared.clone()));
+
+    send_event(&mut world, (TestEvent, Target(child)));
+    schedule.run(&mut world);
+    send_event(&mut world, (TestEvent, Target(root)));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Runs>().0, [1, 2, 3, 4]);
+
+    // unregistering the system removes the callbacks using it
+    world.unregister_callback(shared);
+    assert!(world.resource::<CallbackIndex>().is_empty());
+
+    // so does a panic in the system, instead of failing on every later event
+    fn buggy() {
+        panic!("oops");
+    }
+
+    world.insert_resource(CallbackPanicPolicy::LogAndContinue);
+    world.insert_resource(EventErrorHandler::Collect);
+    let shared = world.register_callback(buggy);
+    for entity in [root, child] {
+        world
+            .entity_mut(entity)
+            .add_callback(On::<TestEvent>::run(shared.clone()));
+    }
+    for _ in 0..2 {
+        send_event(&mut world, (TestEvent, Target(child)));
+        schedule.run(&mut world);
+    }
+    assert_eq!(world.resource::<CallbackPanics>().len(), 1);
+    assert!(world.get_resource::<EventErrors>().is_none());
+    assert!(world.resource::<CallbackIndex>().is_empty());
+}
+
+// this tests if events are dispatched to the callbacks of their source
+#[test]
+fn test_source_callbacks() {
+    use bevy_ecs::system::CommandQueue;
+
+    #[derive(Component)]
+    struct Hit;
+
+    impl Listenable for Hit {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<(Entity, Phase)>);
+
+    fn callback(input: Listener, mut visited: ResMut<Visited>) {
+        visited
+            .0
+            .push((input.current_target().unwrap(), input.phase()));
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+
+    let player = world.spawn_empty().id();
+    let sword = world.spawn_empty().set_parent(player).id();
+    let enemy = world.spawn_empty().id();
+    for entity in [player, sword, enemy] {
+        let mut entity = world.entity_mut(entity);
+        entity.add_callback(On::<Hit>::run(callback));
+        entity.add_callback(On::<Hit>::from_source(callback));
+    }
+
+    let mut run = |world: &mut World, event: (Hit, Target), propagate: bool| {
+        world.resource_mut::<Visited>().0.clear();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        match propagate {
+            true => commands
+                .entity(sword)
+                .send_event_from((event, PropagateSource)),
+            false => commands.entity(sword).send_event_from(event),
+        };
+        queue.apply(world);
+        schedule.run(world);
+        mem::take(&mut world.resource_mut::<Visited>().0)
+    };
+
+    assert_eq!(
+        run(&mut world, (Hit, Target(enemy)), false),
+        [(enemy, Phase::Target), (sword, Phase::Source)]
+    );
+    assert_eq!(
+        run(&mut world, (Hit, Target(enemy)), true),
+        [
+            (enemy, Phase::Target),
+            (sword, Phase::Source),
+            (player, Phase::Source)
+        ]
+    );
+
+    // removing the callbacks by type also removes the source callbacks
+    world.entity_mut(sword).remove_callbacks::<Hit>();
+    assert_eq!(
+        run(&mut world, (Hit, Target(enemy)), true),
+        [(enemy, Phase::Target), (player, Phase::Source)]
+    );
+
+    // the event doesn't reach the source once the target consumes it
+    world.entity_mut(enemy).add_callback(On::<Hit>::run(
+        |input: Listener, mut commands: Commands| {
+            commands.entity(input.id()).despawn();
+        },
+    ));
+    assert_eq!(
+        run(&mut world, (Hit, Target(enemy)), true),
+        [(enemy, Phase::Target)]
+    );
+}
+
+// this tests if events with multiple targets are dispatched to each of them
+#[test]
+fn test_multiple_targets() {
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Explosion;
+
+    impl Listenable for Explosion {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<(Entity, Entity, Entity)>);
+
+    fn callback(input: Listener, mut visited: ResMut<Visited>) {
+        let original = input.original_target().unwrap();
+        let current = input.current_target().unwrap();
+        visited.0.push((input.id(), original, current));
+    }
+
+    #[derive(Resource, Default)]
+    struct Global(Vec<(Entity, Vec<Entity>)>);
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+    world.init_resource::<Global>();
+
+    let parent = world.spawn_empty().id();
+    let a = world.spawn_empty().set_parent(parent).id();
+    let b = world.spawn_empty().id();
+    for entity in [parent, a, b] {
+        world
+            .entity_mut(entity)
+            .add_callback::<Explosion, _>(callback);
+    }
+    // the global callback only runs for the first target
+    let global =
+        world.add_callback::<AnyEvent, _>(|input: Listener, mut global: ResMut<Global>| {
+            let current = input.current_target().unwrap();
+            global.0.push((current, input.targets().to_vec()));
+        });
+
+    let event = send_event(
+        &mut world,
+        (Explosion, [a, b].into_iter().collect::<Targets>()),
+    )
+    .id();
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Visited>().0),
+        [(event, a, a), (event, a, parent), (event, b, b)]
+    );
+    assert_eq!(
+        world.resource::<Global>().0,
+        [(a, vec![a, b]), (parent, vec![a, b])]
+    );
+    world.remove_callback(global);
+
+    // the other targets are skipped once the first target consumes the event
+    world.entity_mut(a).add_callback(On::<Explosion>::run(
+        |input: Listener, mut commands: Commands| {
+            commands.entity(input.id()).despawn();
+        },
+    ));
+    let event = send_event(
+        &mut world,
+        (Explosion, [a, b].into_iter().collect::<Targets>()),
+    )
+    .id();
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [(event, a, a)]);
+}
+
+// this tests if broadcast and tunneled events reach every recipient once
+#[test]
+fn test_broadcast_events() {
+    #[derive(Component)]
+    struct Pause;
+
+    impl Listenable for Pause {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
         }
     }
+
+    #[derive(Component)]
+    struct Ai;
+
+    #[derive(Resource, Default)]
+    struct Visited(Vec<Entity>);
+
+    fn callback(input: Listener, mut visited: ResMut<Visited>) {
+        visited.0.push(input.current_target().unwrap());
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Visited>();
+
+    let root = world.spawn(Ai).id();
+    let a = world.spawn(Ai).set_parent(root).id();
+    let b = world.spawn_empty().set_parent(root).id();
+    let c = world.spawn(Ai).set_parent(b).id();
+    for entity in [root, a, b, c] {
+        world.entity_mut(entity).add_callback::<Pause, _>(callback);
+    }
+    world.add_callback::<Pause, _>(|| {});
+
+    world.broadcast_event::<With<Ai>>(Pause);
+    schedule.run(&mut world);
+    let mut visited = mem::take(&mut world.resource_mut::<Visited>().0);
+    visited.sort();
+    let mut expected = vec![root, a, c];
+    expected.sort();
+    assert_eq!(visited, expected);
+    assert_eq!(world.resource::<EventEntities>().len(), 1);
+
+    // events and callbacks in the hierarchy aren't targets
+    let event = world.spawn(Pause).set_parent(b).id();
+    world.resource_mut::<EventEntities>().push(event);
+    world.tunnel_event(b, Pause);
+    schedule.run(&mut world);
+    assert_eq!(mem::take(&mut world.resource_mut::<Visited>().0), [b, c]);
+
+    world.tunnel_event(root, Pause);
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Visited>().0),
+        [root, a, b, c]
+    );
+
+    // neither are the global callback and the events without a parent
+    world.broadcast_event::<Without<Parent>>(Pause);
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Visited>().0, [root]);
 }
@@ -0,0 +1,278 @@
+use bevy_ecs::{prelude::*, schedule::BoxedCondition, system::SystemState};
+
+use crate::{
+    CallbackId, CallbackIdent, CallbackIndex, CallbackPriority, CallbackSystemInner, RemainingRuns,
+};
+
+/// Where the callbacks added to entities are stored, see [`EventListenerPlugin::with_callback_storage`](crate::EventListenerPlugin::with_callback_storage).
+#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CallbackStorage {
+    /// Spawn the callbacks as [`Children`](bevy_hierarchy::Children) of the entity.
+    #[default]
+    Children,
+    /// Store the callbacks in the [`EventCallbacks`] of the entity, without spawning an entity for each callback.
+    ///
+    /// This keeps them out of the hierarchy, so they aren't despawned by `despawn_descendants`,
+    /// and they are removed along with the entity. Since there are no callback entities to insert components
+    /// like [`Enabled`](crate::Enabled) into, use [`EventCallbacks::get_mut`] to enable them or change their priority.
+    ///
+    /// Only callbacks added with direct world access are stored, callbacks added with
+    /// [`EntityCommands`](bevy_ecs::system::EntityCommands) are always spawned, since their [`CallbackId`] is
+    /// returned before the storage can be looked up.
+    Component,
+}
+
+/// The callbacks of this entity, if they're stored with [`CallbackStorage::Component`].
+#[derive(Component, Default)]
+pub struct EventCallbacks(pub(crate) Vec<StoredCallback>);
+
+impl EventCallbacks {
+    pub fn iter(&self) -> impl Iterator<Item = &StoredCallback> {
+        self.0.iter()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, callback: CallbackId) -> Option<&StoredCallback> {
+        self.0.iter().find(|c| c.id() == callback)
+    }
+
+    pub fn get_mut(&mut self, callback: CallbackId) -> Option<&mut StoredCallback> {
+        self.0.iter_mut().find(|c| c.id() == callback)
+    }
+}
+
+impl<'a> IntoIterator for &'a EventCallbacks {
+    type Item = &'a StoredCallback;
+    type IntoIter = std::slice::Iter<'a, StoredCallback>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A callback in the [`EventCallbacks`] of the entity it was added to.
+pub struct StoredCallback {
+    /// Allocated by the [`CallbackIndex`], see [`CallbackId::Stored`].
+    pub(crate) id: u64,
+    pub(crate) ident: CallbackIdent,
+    /// `None` while the callback is running.
+    pub(crate) system: Option<CallbackSystemInner>,
+    pub(crate) runs: Option<RemainingRuns>,
+    pub(crate) conditions: Vec<BoxedCondition>,
+    pub(crate) priority: CallbackPriority,
+    pub(crate) enabled: bool,
+}
+
+impl StoredCallback {
+    #[inline]
+    pub fn id(&self) -> CallbackId {
+        CallbackId::Stored(self.id)
+    }
+
+    #[inline]
+    pub fn ident(&self) -> &CallbackIdent {
+        &self.ident
+    }
+
+    #[inline]
+    pub fn priority(&self) -> CallbackPriority {
+        self.priority
+    }
+
+    /// Change the priority of the callback, it's re-indexed by [`update_callback_index`](crate::update_callback_index).
+    #[inline]
+    pub fn set_priority(&mut self, priority: CallbackPriority) {
+        self.priority = priority;
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the callback, like the [`Enabled`](crate::Enabled) component of callback entities.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    #[inline]
+    pub fn remaining_runs(&self) -> Option<RemainingRuns> {
+        self.runs
+    }
+}
+
+/// Returns the entity a callback stored in [`EventCallbacks`] was added to, or `None` if it isn't stored there.
+pub(crate) fn stored_owner(world: &World, callback: CallbackId) -> Option<Entity> {
+    let CallbackId::Stored(_) = callback else {
+        return None;
+    };
+    let owner = world.get_resource::<CallbackIndex>()?.owner(callback)?;
+    world
+        .get::<EventCallbacks>(owner)?
+        .get(callback)
+        .map(|_| owner)
+}
+
+/// Returns the callback stored in [`EventCallbacks`], or `None` if it isn't stored there.
+pub(crate) fn stored_callback(world: &World, callback: CallbackId) -> Option<&StoredCallback> {
+    let owner = stored_owner(world, callback)?;
+    world.get::<EventCallbacks>(owner)?.get(callback)
+}
+
+/// Returns the callback stored in [`EventCallbacks`] mutably, or `None` if it isn't stored there.
+pub(crate) fn stored_callback_mut(
+    world: &mut World,
+    callback: CallbackId,
+) -> Option<&mut StoredCallback> {
+    let owner = stored_owner(world, callback)?;
+    world
+        .get_mut::<EventCallbacks>(owner)?
+        .into_inner()
+        .get_mut(callback)
+}
+
+/// Remove the callbacks stored in [`EventCallbacks`] from the [`CallbackIndex`],
+/// after the component was removed from their owner, e.g. because it was despawned.
+pub fn cleanup_event_callbacks(
+    world: &mut World,
+    state: &mut SystemState<RemovedComponents<EventCallbacks>>,
+) {
+    let owners: Vec<Entity> = state.get_mut(world).read().collect();
+    let Some(index) = world.get_resource::<CallbackIndex>() else {
+        return;
+    };
+    // callback entities are removed from the index by `update_callback_index` once they're despawned
+    let stale: Vec<CallbackId> = owners
+        .into_iter()
+        .filter(|&owner| world.get::<EventCallbacks>(owner).is_none())
+        .flat_map(|owner| index.owned(owner))
+        .filter(|callback| matches!(callback, CallbackId::Stored(_)))
+        .collect();
+    let mut index = world.resource_mut::<CallbackIndex>();
+    for callback in stale {
+        index.remove(callback);
+    }
+}
+
+// this tests if callbacks stored in the owner's component behave like child callbacks
+#[test]
+fn test_component_callback_storage() {
+    use crate::*;
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Click;
+
+    impl Listenable for Click {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Clicked(Vec<Entity>);
+
+    fn callback(input: Listener, mut clicked: ResMut<Clicked>) {
+        clicked.0.push(input.current_target().unwrap());
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Clicked>();
+    world.insert_resource(CallbackStorage::Component);
+
+    let panel = world.spawn_empty().id();
+    let button = world.spawn_empty().set_parent(panel).id();
+    let label = world.spawn_empty().set_parent(button).id();
+    world.entity_mut(panel).add_callback::<Click, _>(callback);
+    let on_click = world.entity_mut(button).add_callback::<Click, _>(callback);
+    let once = world
+        .entity_mut(label)
+        .add_callback(On::<Click>::once(callback).run_if(|| true));
+    assert_eq!(world.get::<Children>(button).unwrap().len(), 1);
+    let stored: Vec<CallbackId> = world
+        .get::<EventCallbacks>(button)
+        .unwrap()
+        .iter()
+        .map(StoredCallback::id)
+        .collect();
+    assert_eq!(stored, [on_click]);
+    assert_eq!(on_click.entity(), None);
+    // no entities are spawned for the callbacks
+    assert_eq!(world.entities().len(), 3);
+
+    send_event(&mut world, (Click, Target(label)));
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Clicked>().0),
+        [label, button, panel]
+    );
+    assert!(world.get::<EventCallbacks>(label).unwrap().is_empty());
+    assert!(!world.resource::<CallbackIndex>().contains(once));
+
+    // the callbacks aren't part of the hierarchy
+    world.entity_mut(button).despawn_descendants();
+    send_event(&mut world, (Click, Target(button)));
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Clicked>().0),
+        [button, panel]
+    );
+
+    // stored callbacks are disabled and reprioritized through the component of their owner
+    let placeholder =
+        world
+            .entity_mut(button)
+            .add_callback::<Click, _>(|mut clicked: ResMut<Clicked>| {
+                clicked.0.push(Entity::PLACEHOLDER);
+            });
+    let mut callbacks = world.get_mut::<EventCallbacks>(button).unwrap();
+    callbacks.get_mut(on_click).unwrap().set_enabled(false);
+    send_event(&mut world, (Click, Target(button)));
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Clicked>().0),
+        [Entity::PLACEHOLDER, panel]
+    );
+    let mut callbacks = world.get_mut::<EventCallbacks>(button).unwrap();
+    callbacks.get_mut(on_click).unwrap().set_enabled(true);
+    callbacks
+        .get_mut(placeholder)
+        .unwrap()
+        .set_priority(CallbackPriority(1));
+    send_event(&mut world, (Click, Target(button)));
+    schedule.run(&mut world);
+    assert_eq!(
+        mem::take(&mut world.resource_mut::<Clicked>().0),
+        [Entity::PLACEHOLDER, button, panel]
+    );
+    world.entity_mut(button).remove_callback(placeholder);
+
+    // global callbacks are not affected by removing the callbacks of entities
+    world.add_callback::<Click, _>(callback);
+    world.clear_callbacks();
+    world.entity_mut(panel).remove_callback(on_click);
+    assert!(world.resource::<CallbackIndex>().contains(on_click));
+    world.entity_mut(button).remove_callback(on_click);
+    assert!(!world.resource::<CallbackIndex>().contains(on_click));
+    assert!(world.get::<EventCallbacks>(button).unwrap().is_empty());
+
+    // the callbacks are removed along with their owner
+    world.entity_mut(panel).despawn_recursive();
+    schedule.run(&mut world);
+    assert!(world
+        .query::<&CallbackIdent>()
+        .iter(&world)
+        .next()
+        .is_none());
+    assert!(world.resource::<CallbackIndex>().is_empty());
+}
@@ -0,0 +1,245 @@
+use bevy_ecs::{
+    prelude::*,
+    system::{EntityCommands, SystemState},
+};
+use bevy_utils::smallvec::SmallVec;
+
+use crate::{despawn_callback, insert_callback, CallbackId, IntoCallback, Listenable};
+
+/// A callback of `subscriber`, listening to the events of `publisher`, see [`SubscribeExt::subscribe`].
+#[derive(Component, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Subscription {
+    pub publisher: Entity,
+    pub subscriber: Entity,
+}
+
+impl Subscription {
+    /// Remove the callback from the [`Subscribers`] and [`Subscriptions`] of both sides.
+    pub(crate) fn unlink(&self, world: &mut World, callback: Entity) {
+        if let Some(mut subscribers) = world.get_mut::<Subscribers>(self.publisher) {
+            subscribers.0.retain(|c| *c != callback);
+        }
+        if let Some(mut subscriptions) = world.get_mut::<Subscriptions>(self.subscriber) {
+            subscriptions.0.retain(|c| *c != callback);
+        }
+    }
+}
+
+/// The [`Subscription`] callbacks listening to the events of this entity.
+#[derive(Component, Default, Debug, Clone)]
+pub struct Subscribers(pub(crate) SmallVec<[Entity; 4]>);
+
+impl Subscribers {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl<'a> IntoIterator for &'a Subscribers {
+    type Item = &'a Entity;
+    type IntoIter = std::slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// The [`Subscription`] callbacks of this entity, listening to the events of other entities.
+#[derive(Component, Default, Debug, Clone)]
+pub struct Subscriptions(pub(crate) SmallVec<[Entity; 4]>);
+
+impl Subscriptions {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl<'a> IntoIterator for &'a Subscriptions {
+    type Item = &'a Entity;
+    type IntoIter = std::slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+pub trait SubscribeExt {
+    /// Run a system when the event matching `T` is triggered on `publisher`, like a callback added to `publisher`.
+    ///
+    /// The subscription is removed when either this entity or `publisher` is despawned.
+    fn subscribe<T: Listenable, M>(
+        &mut self,
+        publisher: Entity,
+        callback: impl IntoCallback<T, M>,
+    ) -> CallbackId;
+
+    /// Remove all subscriptions of this entity to `publisher`.
+    fn unsubscribe(&mut self, publisher: Entity) -> &mut Self;
+}
+
+impl<'w> SubscribeExt for EntityWorldMut<'w> {
+    fn subscribe<T: Listenable, M>(
+        &mut self,
+        publisher: Entity,
+        callback: impl IntoCallback<T, M>,
+    ) -> CallbackId {
+        let subscriber = self.id();
+        self.world_scope(|world| {
+            let on = callback.into_callback();
+            insert_callback(world, None, Some(publisher), Some(subscriber), on)
+        })
+    }
+
+    fn unsubscribe(&mut self, publisher: Entity) -> &mut Self {
+        let subscriber = self.id();
+        self.world_scope(|world| {
+            let callbacks: Vec<CallbackId> = world
+                .get::<Subscriptions>(subscriber)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&c| {
+                    world
+                        .get::<Subscription>(c)
+                        .is_some_and(|s| s.publisher == publisher)
+                })
+                .map(CallbackId::Entity)
+                .collect();
+            for callback in callbacks {
+                despawn_callback(world, callback);
+            }
+        });
+        self
+    }
+}
+
+impl<'a> SubscribeExt for EntityCommands<'a> {
+    fn subscribe<T: Listenable, M>(
+        &mut self,
+        publisher: Entity,
+        callback: impl IntoCallback<T, M>,
+    ) -> CallbackId {
+        let subscriber = self.id();
+        let entity = self.commands().spawn_empty().id();
+        self.commands().add(move |world: &mut World| {
+            let alive = |e| world.get_entity(e).is_some();
+            if alive(subscriber) && alive(publisher) {
+                let on = callback.into_callback();
+                insert_callback(world, Some(entity), Some(publisher), Some(subscriber), on);
+            } else {
+                world.despawn(entity);
+            }
+        });
+        CallbackId::Entity(entity)
+    }
+
+    fn unsubscribe(&mut self, publisher: Entity) -> &mut Self {
+        self.add(move |mut entity: EntityWorldMut| {
+            entity.unsubscribe(publisher);
+        })
+    }
+}
+
+/// Despawn the subscriptions whose publisher or subscriber was despawned.
+#[allow(clippy::type_complexity)]
+pub fn cleanup_subscriptions(
+    world: &mut World,
+    state: &mut SystemState<(
+        Query<(Entity, &Subscription)>,
+        RemovedComponents<Subscribers>,
+        RemovedComponents<Subscriptions>,
+    )>,
+) {
+    let (subscriptions, mut removed_subscribers, mut removed_subscriptions) = state.get_mut(world);
+    // only check the subscriptions if either side might have been despawned
+    let removed = removed_subscribers.read().count() + removed_subscriptions.read().count();
+    if removed == 0 {
+        return;
+    }
+    let subscriptions: Vec<(Entity, Subscription)> =
+        subscriptions.iter().map(|(e, s)| (e, *s)).collect();
+    for (callback, subscription) in subscriptions {
+        let entities = world.entities();
+        if !entities.contains(subscription.publisher) || !entities.contains(subscription.subscriber)
+        {
+            despawn_callback(world, CallbackId::Entity(callback));
+        }
+    }
+}
+
+// this tests if subscribers run their callbacks and are cleaned up with either side
+#[test]
+fn test_subscriptions() {
+    use crate::*;
+    use bevy_event_entities_core::send_event;
+
+    #[derive(Component)]
+    struct Damage;
+
+    impl Listenable for Damage {
+        fn entity_contains(entity: EntityRef) -> bool {
+            entity.contains::<Self>()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Updated(Vec<Entity>);
+
+    fn update_health_bar(input: Listener, mut updated: ResMut<Updated>) {
+        updated.0.push(input.current_target().unwrap());
+    }
+
+    let (mut world, mut schedule) = test_world();
+    world.init_resource::<Updated>();
+
+    let player = world.spawn_empty().id();
+    let enemy = world.spawn_empty().id();
+    let health_bar = world.spawn_empty().id();
+    world
+        .entity_mut(health_bar)
+        .subscribe::<Damage, _>(player, update_health_bar);
+    let enemy_bar = world
+        .entity_mut(health_bar)
+        .subscribe::<Damage, _>(enemy, update_health_bar);
+    assert_eq!(world.get::<Subscriptions>(health_bar).unwrap().0.len(), 2);
+    assert_eq!(world.get::<Subscribers>(player).unwrap().0.len(), 1);
+
+    send_event(&mut world, (Damage, Target(player)));
+    send_event(&mut world, (Damage, Target(health_bar)));
+    schedule.run(&mut world);
+    assert_eq!(mem::take(&mut world.resource_mut::<Updated>().0), [player]);
+
+    // removing the global callbacks keeps the subscriptions
+    world.add_callback::<Damage, _>(update_health_bar);
+    world.remove_callbacks::<Damage>();
+    world.add_callback::<Damage, _>(update_health_bar);
+    world.clear_callbacks();
+    assert_eq!(world.get::<Subscriptions>(health_bar).unwrap().0.len(), 2);
+    assert_eq!(world.resource::<CallbackIndex>().len(), 2);
+
+    // despawning the publisher removes the subscription
+    world.entity_mut(enemy).despawn_recursive();
+    schedule.run(&mut world);
+    assert!(world.get_entity(enemy_bar.entity().unwrap()).is_none());
+    assert_eq!(world.get::<Subscriptions>(health_bar).unwrap().0.len(), 1);
+    assert_eq!(world.resource::<CallbackIndex>().len(), 1);
+
+    // despawning the subscriber removes the subscription
+    world.entity_mut(health_bar).despawn_recursive();
+    send_event(&mut world, (Damage, Target(player)));
+    schedule.run(&mut world);
+    assert!(world.resource::<Updated>().0.is_empty());
+    assert!(world.get::<Subscribers>(player).unwrap().0.is_empty());
+    assert!(world.resource::<CallbackIndex>().is_empty());
+
+    // subscriptions can also be removed explicitly
+    let health_bar = world.spawn_empty().id();
+    world
+        .entity_mut(health_bar)
+        .subscribe::<Damage, _>(player, update_health_bar);
+    world.entity_mut(health_bar).unsubscribe(player);
+    send_event(&mut world, (Damage, Target(player)));
+    schedule.run(&mut world);
+    assert!(world.resource::<Updated>().0.is_empty());
+    assert!(world.resource::<CallbackIndex>().is_empty());
+}
@@ -0,0 +1,23 @@
+use bevy_ecs::{prelude::*, world::EntityRef};
+use bevy_event_entities_core::EventEntities;
+
+use crate::{event_listener_system_configs, Listenable};
+
+/// The event of tests that only need one kind of event.
+#[derive(Component)]
+pub(crate) struct TestEvent;
+
+impl Listenable for TestEvent {
+    fn entity_contains(entity: EntityRef) -> bool {
+        entity.contains::<Self>()
+    }
+}
+
+/// Create a world with the `EventEntities` resource, and a schedule running the listener systems.
+pub(crate) fn test_world() -> (World, Schedule) {
+    let mut world = World::new();
+    world.init_resource::<EventEntities>();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(event_listener_system_configs());
+    (world, schedule)
+}
@@ -2,17 +2,20 @@ use bevy::prelude::*;
 use bevy_event_entities::{event_listener::Target, prelude::*};
 
 fn main() {
-    App::new()
-        .add_plugins((DefaultPlugins, EventPlugin::default()))
+    let mut app = App::new();
+    app.add_plugins((DefaultPlugins, EventPlugin::default()))
         .add_systems(Startup, setup)
-        .add_systems(Update, attack_enemy)
-        // `process_kill` have to run after `process_attack` since it's referencing the entity for the `Attack` event
-        // and event entities are despawned every update.
-        .add_systems(
-            PostUpdate,
-            (block_attack, process_attack, defy_death, process_kill).chain(),
-        )
-        .run()
+        .add_systems(Update, attack_enemy);
+
+    // Each stage runs after the previous one, and skips the events that were despawned by an earlier stage.
+    // `process_kill` is referencing the entity for the `Attack` event, so it's part of the same pipeline.
+    app.add_event_pipeline::<Attack>()
+        .stage(block_attack)
+        .stage(process_attack)
+        .stage(defy_death)
+        .stage(process_kill);
+
+    app.run()
 }
 
 #[derive(Component)]
@@ -43,29 +43,29 @@ fn setup(mut commands: Commands) {
         warn!("whoa whoa whoa now, it seems a lot like someone was attacked over here");
     });
 
-    commands
-        .spawn((Player, Name::new("Goblin"), Health(10)))
-        .add_callback::<Attack, _>(block_or_take_damage)
-        .with_children(|parent| {
-            parent
-                .spawn((Armor, Name::new("Helmet"), Health(2)))
-                .add_callback::<Attack, _>(block_or_take_damage);
+    let mut player = commands.spawn((Player, Name::new("Goblin"), Health(10)));
+    player.add_callback::<Attack, _>(block_or_take_damage);
+    player.with_children(|parent| {
+        parent
+            .spawn((Armor, Name::new("Helmet"), Health(2)))
+            .add_callback::<Attack, _>(block_or_take_damage);
 
-            parent
-                .spawn((Armor, Name::new("Shirt"), Health(5)))
-                .add_callback::<Attack, _>(block_or_take_damage);
-            parent
-                .spawn((Armor, Name::new("Socks"), Health(2)))
-                .add_callback::<Attack, _>(block_or_take_damage);
-        });
+        parent
+            .spawn((Armor, Name::new("Shirt"), Health(5)))
+            .add_callback::<Attack, _>(block_or_take_damage);
+        parent
+            .spawn((Armor, Name::new("Socks"), Health(2)))
+            .add_callback::<Attack, _>(block_or_take_damage);
+    });
 }
 
 fn block_or_take_damage(
     mut commands: Commands,
-    mut input: Listener<(Entity, &mut Attack, &Target)>,
+    mut input: Listener<(Entity, &mut Attack)>,
     mut health: Query<(&mut Health, &Name)>,
 ) {
-    let (event, mut attack, &Target(target)) = input.event_mut();
+    let target = input.current_target().unwrap();
+    let (event, mut attack) = input.event_mut();
     let (mut health, name) = health.get_mut(target).unwrap();
 
     let new_health = health.0.saturating_sub(attack.damage);